@@ -6,6 +6,8 @@
 /// de contratos off-chain, demostrando el uso de Arkiv en Polkadot.
 /// 
 /// Generado con Polkadot DevKit para sub0 Hackathon 2025
+pub use self::arkiv_storage::{ArkivStorage, ArkivStorageRef};
+
 #[ink::contract]
 mod arkiv_storage {
     use ink::storage::Mapping;
@@ -23,6 +25,10 @@ mod arkiv_storage {
         contract_count: u32,
         /// Dirección del contrato Arkiv (off-chain)
         arkiv_address: Option<String>,
+        /// Owner del storage (puede ser un contrato de gobernanza)
+        owner: AccountId,
+        /// Owner propuesto, pendiente de aceptar
+        pending_owner: Option<AccountId>,
     }
 
     /// Información de un contrato
@@ -72,6 +78,22 @@ mod arkiv_storage {
         arkiv_address: String,
     }
 
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
     impl ArkivStorage {
         /// Constructor del contrato
         #[ink(constructor)]
@@ -81,6 +103,8 @@ mod arkiv_storage {
                 contract_list: Vec::new(),
                 contract_count: 0,
                 arkiv_address: None,
+                owner: Self::env().caller(),
+                pending_owner: None,
             }
         }
 
@@ -170,10 +194,10 @@ mod arkiv_storage {
             Ok(())
         }
 
-        /// Establecer dirección de Arkiv (off-chain)
+        /// Establecer dirección de Arkiv (off-chain) (solo owner)
         #[ink(message)]
         pub fn set_arkiv_address(&mut self, arkiv_address: String) -> Result<(), Error> {
-            let caller = Self::env().caller();
+            self.ensure_owner()?;
             self.arkiv_address = Some(arkiv_address.clone());
 
             Self::env().emit_event(ArkivAddressSet {
@@ -183,6 +207,56 @@ mod arkiv_storage {
             Ok(())
         }
 
+        /// Proponer un nuevo owner (solo owner)
+        ///
+        /// El cambio se completa cuando `new_owner` llama a `accept_ownership`.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.pending_owner = Some(new_owner);
+
+            Self::env().emit_event(OwnershipTransferStarted {
+                previous_owner: self.owner,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Aceptar el ownership propuesto
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+
+            if self.pending_owner != Some(caller) {
+                return Err(Error::NotOwner);
+            }
+
+            let previous_owner = self.owner;
+            self.owner = caller;
+            self.pending_owner = None;
+
+            Self::env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Obtener el owner
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Obtener el owner pendiente de aceptar
+        #[ink(message)]
+        pub fn get_pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner
+        }
+
         /// Obtener información de un contrato
         #[ink(message)]
         pub fn get_contract(&self, contract_address: AccountId) -> Option<ContractInfo> {
@@ -234,6 +308,14 @@ mod arkiv_storage {
             }
             results
         }
+
+        /// Verificar que el caller es el owner
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if Self::env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
     }
 
     /// Errores del contrato
@@ -243,6 +325,64 @@ mod arkiv_storage {
         ContractAlreadyRegistered,
         ContractNotFound,
         InvalidArkivAddress,
+        NotOwner,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+        use ink::env::DefaultEnvironment;
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        fn storage() -> ArkivStorage {
+            set_caller(accounts().alice);
+            ArkivStorage::new()
+        }
+
+        #[ink::test]
+        fn set_arkiv_address_requires_owner() {
+            let mut storage = storage();
+            assert_eq!(storage.get_owner(), accounts().alice);
+
+            set_caller(accounts().bob);
+            assert_eq!(storage.set_arkiv_address(String::from("0xarkiv")), Err(Error::NotOwner));
+            assert_eq!(storage.get_arkiv_address(), None);
+
+            set_caller(accounts().alice);
+            storage.set_arkiv_address(String::from("0xarkiv")).unwrap();
+            assert_eq!(storage.get_arkiv_address(), Some(String::from("0xarkiv")));
+        }
+
+        #[ink::test]
+        fn ownership_transfer_is_two_step() {
+            let mut storage = storage();
+            set_caller(accounts().bob);
+            assert_eq!(storage.transfer_ownership(accounts().bob), Err(Error::NotOwner));
+
+            set_caller(accounts().alice);
+            storage.transfer_ownership(accounts().bob).unwrap();
+            assert_eq!(storage.get_owner(), accounts().alice);
+            assert_eq!(storage.get_pending_owner(), Some(accounts().bob));
+
+            set_caller(accounts().charlie);
+            assert_eq!(storage.accept_ownership(), Err(Error::NotOwner));
+
+            set_caller(accounts().bob);
+            storage.accept_ownership().unwrap();
+            assert_eq!(storage.get_owner(), accounts().bob);
+            assert_eq!(storage.get_pending_owner(), None);
+
+            set_caller(accounts().alice);
+            assert_eq!(storage.set_arkiv_address(String::from("0xarkiv")), Err(Error::NotOwner));
+        }
     }
 }
 
@@ -8,45 +8,106 @@
 /// Generado con Polkadot DevKit para sub0 Hackathon 2025
 #[ink::contract]
 mod devkit_showcase {
-    use ink::storage::Mapping;
+    use ink::storage::{Lazy, Mapping};
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
+    use ink::env::call::{build_call, ExecutionInput, FromAccountId, Selector};
+    use ink::codegen::TraitCallBuilder;
+    use hyperbridge_oracle::HyperbridgeOracleRef;
+    use arkiv_storage::ArkivStorageRef;
+    #[cfg(not(test))]
+    use governance_token::{GovernanceTokenRef, Votes};
+
+    /// Track por defecto creado en el constructor
+    pub const DEFAULT_TRACK: u16 = 0;
+
+    /// Precisión del acumulador de recompensas por token
+    const REWARD_PRECISION: Balance = 1_000_000_000_000;
+
+    /// Milisegundos en un año, para anualizar en modo timestamp
+    const MILLIS_PER_YEAR: u128 = 365 * 24 * 60 * 60 * 1000;
+
+    /// Bloques en un año asumiendo bloques de 6 segundos
+    const BLOCKS_PER_YEAR: u128 = 365 * 24 * 60 * 10;
+
+    /// Máximo de tokens de recompensa (cada operación de staking los recorre)
+    pub const MAX_REWARD_TOKENS: usize = 8;
 
     /// Storage del contrato
     #[ink(storage)]
     pub struct DevKitShowcase {
+        /// Administrador (registra tokens de recompensa)
+        admin: AccountId,
         /// Token de gobernanza
         governance_token: AccountId,
+        /// Unidad de tiempo de todos los períodos (bloques o timestamp)
+        clock_mode: ClockMode,
         /// Propuestas activas
         proposals: Mapping<u32, Proposal>,
         /// Contador de propuestas
         proposal_count: u32,
-        /// Período de votación en bloques
-        voting_period: u64,
-        /// Umbral de quórum (en tokens)
-        quorum_threshold: Balance,
+        /// Tracks de gobernanza con sus parámetros
+        tracks: Mapping<u16, Track>,
+        /// Lista de tracks configurados
+        track_ids: Vec<u16>,
         /// Pool de staking
         staking_pool: Balance,
         /// Información de stakers
         stakers: Mapping<AccountId, StakerInfo>,
-        /// Tasa de recompensas por bloque
+        /// Tasa de recompensas por unidad de reloj
         reward_rate: Balance,
         /// Última actualización de recompensas
         last_reward_update: u64,
+        /// Recompensas acumuladas por token staked (escaladas por REWARD_PRECISION)
+        reward_per_token_stored: Balance,
+        /// Fondos del tesoro (stake slasheado)
+        treasury_balance: Balance,
+        /// Bloqueos de stake por votos con convicción
+        vote_locks: Mapping<AccountId, VoteLock>,
+        /// Consejo de guardianes con derecho a veto
+        guardians: Vec<AccountId>,
+        /// Firmas de guardianes necesarias para vetar
+        guardian_threshold: u32,
+        /// Tokens PSP22 de recompensa registrados
+        reward_tokens: Vec<AccountId>,
+        /// Estado de cada token de recompensa
+        reward_token_info: Mapping<AccountId, RewardToken>,
+        /// Recompensas por (token, staker)
+        token_rewards: Mapping<(AccountId, AccountId), TokenRewardState>,
+        /// Lock de reentrada; `Lazy` se escribe al instante, no al final del mensaje
+        reentrancy_lock: Lazy<bool>,
+    }
+
+    /// Unidad en la que se miden los períodos del contrato
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ClockMode {
+        /// Número de bloque
+        BlockNumber,
+        /// Timestamp del bloque en milisegundos
+        Timestamp,
     }
 
     /// Información de una propuesta
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct Proposal {
         /// Creador de la propuesta
         proposer: AccountId,
+        /// Track de gobernanza
+        track_id: u16,
+        /// Tipo de propuesta
+        kind: ProposalKind,
         /// Descripción de la propuesta
         description: String,
+        /// Acción a ejecutar si se aprueba
+        action: Option<ProposalAction>,
         /// Tiempo de inicio
         start_time: u64,
         /// Tiempo de fin
         end_time: u64,
+        /// Bloque cuyo poder de voto en `governance_token` cuenta
+        snapshot_block: BlockNumber,
         /// Votos a favor
         for_votes: Balance,
         /// Votos en contra
@@ -55,99 +116,588 @@ mod devkit_showcase {
         executed: bool,
         /// Votantes
         voters: Vec<AccountId>,
+        /// Guardianes que han firmado el veto
+        guardian_vetoes: Vec<AccountId>,
+        /// Vetada por el consejo de guardianes
+        vetoed: bool,
+    }
+
+    /// Tipo de propuesta
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ProposalKind {
+        /// Requiere quórum y mayoría según el track
+        Majority,
+        /// Se aprueba al terminar la votación salvo que las objeciones
+        /// superen el umbral de veto del track
+        Optimistic,
+    }
+
+    /// Parámetros de un track de gobernanza
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Track {
+        /// Nombre del track (ej. "Treasury-small")
+        pub name: String,
+        /// Período de votación (en unidades del reloj)
+        pub voting_period: u64,
+        /// Umbral de quórum (en tokens)
+        pub quorum_threshold: Balance,
+        /// Porcentaje de aprobación requerido (basis points sobre los votos emitidos)
+        pub approval_threshold: u16,
+        /// Espera entre el fin de la votación y la ejecución (en unidades del reloj)
+        pub timelock: u64,
+        /// Stake mínimo requerido para proponer
+        pub min_proposer_stake: Balance,
+        /// Objeciones (votos en contra) que bloquean una propuesta optimista
+        pub veto_threshold: Balance,
+        /// Admite propuestas optimistas (solo con acciones de bajo riesgo)
+        pub optimistic_allowed: bool,
+    }
+
+    /// Acción ejecutada por una propuesta aprobada
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ProposalAction {
+        /// Crear o reemplazar la configuración de un track
+        SetTrack { track_id: u16, track: Track },
+        /// Recortar el stake de una cuenta
+        Slash {
+            account: AccountId,
+            basis_points: u16,
+            reason: String,
+            destination: SlashDestination,
+        },
+        /// Reemplazar el consejo de guardianes
+        SetGuardians {
+            guardians: Vec<AccountId>,
+            threshold: u32,
+        },
+        /// Cambiar la tasa de recompensas
+        SetRewardRate { reward_rate: Balance },
+        /// Aceptar el ownership de un `HyperbridgeOracle` ya transferido a la DAO
+        AcceptOracleOwnership { oracle: AccountId },
+        /// Agregar un validador en un `HyperbridgeOracle`
+        AddOracleValidator { oracle: AccountId, validator: AccountId },
+        /// Establecer la dirección de Arkiv en un `ArkivStorage`
+        SetArkivAddress { storage: AccountId, arkiv_address: String },
+        /// Aceptar el ownership de un `ArkivStorage` ya transferido a la DAO
+        AcceptArkivOwnership { storage: AccountId },
+    }
+
+    impl ProposalAction {
+        /// Acción operativa de bajo riesgo, apta para propuestas optimistas
+        ///
+        /// Las que tocan gobernanza, stake, recompensas o contratos administrados
+        /// por la DAO requieren una propuesta por mayoría.
+        pub fn is_low_risk(&self) -> bool {
+            matches!(self, ProposalAction::SetArkivAddress { .. })
+        }
+    }
+
+    /// Destino de los fondos slasheados
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum SlashDestination {
+        /// Enviar al tesoro del contrato
+        Treasury,
+        /// Quemar
+        Burn,
+    }
+
+    /// Token PSP22 de recompensa con su propio acumulador
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RewardToken {
+        /// Tasa de recompensas por unidad de reloj
+        pub rate: Balance,
+        /// Recompensas acumuladas por token staked (escaladas por REWARD_PRECISION)
+        pub reward_per_token_stored: Balance,
+        /// Fondos depositados aún no asignados a stakers
+        pub reserve: Balance,
+        /// Última actualización del acumulador
+        pub last_update: u64,
+    }
+
+    /// Recompensas de un staker en un token
+    #[derive(Debug, Clone, Default, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TokenRewardState {
+        /// Valor del acumulador en la última liquidación
+        reward_per_token_paid: Balance,
+        /// Recompensas pendientes de reclamar
+        owed: Balance,
+    }
+
+    /// Errores estándar de PSP22, para decodificar las respuestas del token
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
     }
 
     /// Información de un staker
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct StakerInfo {
         /// Cantidad staked
         amount: Balance,
         /// Deuda de recompensas
         reward_debt: Balance,
+        /// Valor del acumulador en la última liquidación
+        reward_per_token_paid: Balance,
         /// Última actualización
         last_update: u64,
     }
 
+    /// Nivel de convicción de un voto (estilo OpenGov)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Conviction {
+        /// 0.1x, sin bloqueo
+        None,
+        /// 1x, bloqueo de 1 período
+        Locked1x,
+        /// 2x, bloqueo de 2 períodos
+        Locked2x,
+        /// 3x, bloqueo de 4 períodos
+        Locked3x,
+        /// 4x, bloqueo de 8 períodos
+        Locked4x,
+        /// 5x, bloqueo de 16 períodos
+        Locked5x,
+        /// 6x, bloqueo de 32 períodos
+        Locked6x,
+    }
+
+    impl Conviction {
+        /// Multiplicador de votos en décimas (0.1x = 1, 6x = 60)
+        pub fn multiplier_tenths(&self) -> Balance {
+            match self {
+                Conviction::None => 1,
+                Conviction::Locked1x => 10,
+                Conviction::Locked2x => 20,
+                Conviction::Locked3x => 30,
+                Conviction::Locked4x => 40,
+                Conviction::Locked5x => 50,
+                Conviction::Locked6x => 60,
+            }
+        }
+
+        /// Votos efectivos de `balance` con esta convicción
+        ///
+        /// Se divide antes de multiplicar para que 1x no desborde con
+        /// balances cercanos a `Balance::MAX`.
+        pub fn votes(&self, balance: Balance) -> Option<Balance> {
+            let multiplier = self.multiplier_tenths();
+            (balance / 10)
+                .checked_mul(multiplier)?
+                .checked_add(balance % 10 * multiplier / 10)
+        }
+
+        /// Número de períodos de votación que dura el bloqueo
+        pub fn lock_periods(&self) -> u64 {
+            match self {
+                Conviction::None => 0,
+                Conviction::Locked1x => 1,
+                Conviction::Locked2x => 2,
+                Conviction::Locked3x => 4,
+                Conviction::Locked4x => 8,
+                Conviction::Locked5x => 16,
+                Conviction::Locked6x => 32,
+            }
+        }
+    }
+
+    /// Bloqueo de stake por votos con convicción
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct VoteLock {
+        /// Cantidad bloqueada
+        amount: Balance,
+        /// Tiempo a partir del cual se puede desbloquear
+        unlock_at: u64,
+    }
+
+    /// Estado de una propuesta, emitido en `ProposalStateChanged`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ProposalState {
+        /// Creada y abierta a votación
+        Active,
+        /// Vetada por el consejo de guardianes
+        Vetoed,
+        /// Ejecutada
+        Executed,
+    }
+
+    /// Parámetro de gobernanza modificado, emitido en `ParameterChanged`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Parameter {
+        /// Configuración de un track
+        Track { track_id: u16, track: Track },
+        /// Consejo de guardianes
+        Guardians { guardians: Vec<AccountId>, threshold: u32 },
+        /// Oracle cuyo ownership pasó a la DAO
+        OracleOwner { oracle: AccountId },
+        /// Validador agregado en un oracle
+        OracleValidator { oracle: AccountId, validator: AccountId },
+        /// Dirección de Arkiv de un storage
+        ArkivAddress { storage: AccountId, arkiv_address: String },
+        /// Storage cuyo ownership pasó a la DAO
+        ArkivOwner { storage: AccountId },
+    }
+
     /// Eventos del contrato
+    ///
+    /// Las propuestas usan `proposal_id` como primer topic y las cuentas
+    /// (`proposer`, `voter`, `staker`, `guardian`) como segundo.
     #[ink(event)]
     pub struct ProposalCreated {
         #[ink(topic)]
-        proposal_id: u32,
+        pub proposal_id: u32,
         #[ink(topic)]
-        proposer: AccountId,
-        description: String,
+        pub proposer: AccountId,
+        #[ink(topic)]
+        pub track_id: u16,
+        pub kind: ProposalKind,
+        pub description: String,
+    }
+
+    #[ink(event)]
+    pub struct ProposalStateChanged {
+        #[ink(topic)]
+        pub proposal_id: u32,
+        pub state: ProposalState,
     }
 
     #[ink(event)]
     pub struct Voted {
         #[ink(topic)]
-        proposal_id: u32,
+        pub proposal_id: u32,
+        #[ink(topic)]
+        pub voter: AccountId,
+        pub support: bool,
+        pub votes: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VotedWithConviction {
+        #[ink(topic)]
+        pub proposal_id: u32,
         #[ink(topic)]
-        voter: AccountId,
-        support: bool,
-        votes: Balance,
+        pub voter: AccountId,
+        pub support: bool,
+        pub balance: Balance,
+        pub conviction: Conviction,
+        pub votes: Balance,
+        pub unlock_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct GuardianVetoCast {
+        #[ink(topic)]
+        pub proposal_id: u32,
+        #[ink(topic)]
+        pub guardian: AccountId,
+        pub signatures: u32,
     }
 
     #[ink(event)]
     pub struct ProposalExecuted {
         #[ink(topic)]
-        proposal_id: u32,
+        pub proposal_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct ParameterChanged {
+        #[ink(topic)]
+        pub proposal_id: u32,
+        pub parameter: Parameter,
+    }
+
+    #[ink(event)]
+    pub struct RewardRateChanged {
+        #[ink(topic)]
+        pub proposal_id: u32,
+        pub old_rate: Balance,
+        pub new_rate: Balance,
     }
 
     #[ink(event)]
     pub struct Staked {
         #[ink(topic)]
-        staker: AccountId,
-        amount: Balance,
+        pub staker: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        pub staker: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unlocked {
+        #[ink(topic)]
+        pub staker: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Slashed {
+        #[ink(topic)]
+        pub proposal_id: u32,
+        #[ink(topic)]
+        pub account: AccountId,
+        pub amount: Balance,
+        pub reason: String,
+        pub destination: SlashDestination,
     }
 
     #[ink(event)]
     pub struct RewardsClaimed {
         #[ink(topic)]
-        staker: AccountId,
-        amount: Balance,
+        pub staker: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardTokenAdded {
+        #[ink(topic)]
+        pub token: AccountId,
+        pub rate: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardTokenRateChanged {
+        #[ink(topic)]
+        pub token: AccountId,
+        pub old_rate: Balance,
+        pub new_rate: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardTokenFunded {
+        #[ink(topic)]
+        pub token: AccountId,
+        #[ink(topic)]
+        pub funder: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TokenRewardsClaimed {
+        #[ink(topic)]
+        pub token: AccountId,
+        #[ink(topic)]
+        pub staker: AccountId,
+        pub amount: Balance,
     }
 
     impl DevKitShowcase {
         /// Constructor del contrato
+        ///
+        /// `voting_period` y `quorum_threshold` configuran el track por defecto
+        /// (id 0), que exige mayoría simple y no tiene timelock. `clock_mode`
+        /// fija si `voting_period` y `reward_rate` se expresan en bloques o en
+        /// milisegundos.
         #[ink(constructor)]
         pub fn new(
             governance_token: AccountId,
             voting_period: u64,
             quorum_threshold: Balance,
             reward_rate: Balance,
+            clock_mode: ClockMode,
         ) -> Self {
+            let mut tracks = Mapping::default();
+            tracks.insert(DEFAULT_TRACK, &Track {
+                name: String::from("Default"),
+                voting_period,
+                quorum_threshold,
+                approval_threshold: 5000,
+                timelock: 0,
+                min_proposer_stake: 0,
+                veto_threshold: quorum_threshold,
+                optimistic_allowed: true,
+            });
+
             Self {
+                admin: Self::env().caller(),
                 governance_token,
+                clock_mode,
                 proposals: Mapping::default(),
                 proposal_count: 0,
-                voting_period,
-                quorum_threshold,
+                tracks,
+                track_ids: ink::prelude::vec![DEFAULT_TRACK],
                 staking_pool: 0,
                 stakers: Mapping::default(),
                 reward_rate,
-                last_reward_update: Self::env().block_timestamp(),
+                last_reward_update: Self::clock(clock_mode),
+                reward_per_token_stored: 0,
+                treasury_balance: 0,
+                vote_locks: Mapping::default(),
+                guardians: Vec::new(),
+                guardian_threshold: 0,
+                reward_tokens: Vec::new(),
+                reward_token_info: Mapping::default(),
+                token_rewards: Mapping::default(),
+                reentrancy_lock: Lazy::default(),
             }
         }
 
-        /// Crear una nueva propuesta
+        /// Crear una nueva propuesta en un track
+        #[ink(message)]
+        pub fn propose(
+            &mut self,
+            track_id: u16,
+            description: String,
+            action: Option<ProposalAction>,
+        ) -> Result<u32, Error> {
+            self.create_proposal(track_id, ProposalKind::Majority, description, action)
+        }
+
+        /// Crear una propuesta optimista en un track
+        ///
+        /// Se aprueba al terminar el período de votación salvo que los votos
+        /// en contra superen `veto_threshold` o el consejo de guardianes la vete.
+        /// Solo en tracks con `optimistic_allowed` y sin acción o con una de
+        /// bajo riesgo (`ProposalAction::is_low_risk`).
+        #[ink(message)]
+        pub fn propose_optimistic(
+            &mut self,
+            track_id: u16,
+            description: String,
+            action: Option<ProposalAction>,
+        ) -> Result<u32, Error> {
+            self.create_proposal(track_id, ProposalKind::Optimistic, description, action)
+        }
+
+        /// Vetar una propuesta como guardián
+        ///
+        /// La propuesta queda vetada cuando firman `guardian_threshold` guardianes.
         #[ink(message)]
-        pub fn propose(&mut self, description: String) -> Result<u32, Error> {
+        pub fn guardian_veto(&mut self, proposal_id: u32) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if !self.guardians.contains(&caller) {
+                return Err(Error::NotGuardian);
+            }
+
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            if proposal.executed {
+                return Err(Error::AlreadyExecuted);
+            }
+
+            if proposal.vetoed {
+                return Err(Error::ProposalVetoed);
+            }
+
+            if proposal.guardian_vetoes.contains(&caller) {
+                return Err(Error::AlreadyVetoed);
+            }
+
+            proposal.guardian_vetoes.push(caller);
+            let signatures = proposal.guardian_vetoes.len() as u32;
+            if signatures >= self.guardian_threshold {
+                proposal.vetoed = true;
+            }
+            self.proposals.insert(proposal_id, &proposal);
+
+            Self::env().emit_event(GuardianVetoCast {
+                proposal_id,
+                guardian: caller,
+                signatures,
+            });
+
+            if proposal.vetoed {
+                Self::env().emit_event(ProposalStateChanged {
+                    proposal_id,
+                    state: ProposalState::Vetoed,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Crear una propuesta del tipo indicado
+        fn create_proposal(
+            &mut self,
+            track_id: u16,
+            kind: ProposalKind,
+            description: String,
+            action: Option<ProposalAction>,
+        ) -> Result<u32, Error> {
             let caller = Self::env().caller();
+            let track = self.tracks.get(track_id)
+                .ok_or(Error::TrackNotFound)?;
+
+            let proposer_stake = self.stakers.get(caller)
+                .map(|info| info.amount)
+                .unwrap_or(0);
+            if proposer_stake < track.min_proposer_stake {
+                return Err(Error::InsufficientProposerStake);
+            }
+
+            if kind == ProposalKind::Optimistic {
+                Self::ensure_optimistic_allowed(&track, &action)?;
+            }
+
+            match &action {
+                Some(ProposalAction::SetTrack { track: new_track, .. }) => {
+                    Self::validate_track(new_track)?;
+                }
+                Some(ProposalAction::Slash { basis_points, .. }) => {
+                    if *basis_points == 0 || *basis_points > 10_000 {
+                        return Err(Error::InvalidSlash);
+                    }
+                }
+                Some(ProposalAction::SetGuardians { guardians, threshold }) => {
+                    Self::validate_guardians(guardians, *threshold)?;
+                }
+                Some(ProposalAction::SetArkivAddress { arkiv_address, .. }) => {
+                    if arkiv_address.is_empty() {
+                        return Err(Error::InvalidAction);
+                    }
+                }
+                Some(ProposalAction::SetRewardRate { .. })
+                | Some(ProposalAction::AcceptOracleOwnership { .. })
+                | Some(ProposalAction::AddOracleValidator { .. })
+                | Some(ProposalAction::AcceptArkivOwnership { .. })
+                | None => {}
+            }
+
             let proposal_id = self.proposal_count;
-            self.proposal_count += 1;
+            self.proposal_count = self.proposal_count
+                .checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
 
-            let now = Self::env().block_timestamp();
+            let now = self.now();
             let proposal = Proposal {
                 proposer: caller,
+                track_id,
+                kind,
                 description: description.clone(),
+                action,
                 start_time: now,
-                end_time: now + self.voting_period,
+                end_time: now
+                    .checked_add(track.voting_period)
+                    .ok_or(Error::ArithmeticOverflow)?,
+                snapshot_block: Self::env().block_number().saturating_sub(1),
                 for_votes: 0,
                 against_votes: 0,
                 executed: false,
                 voters: Vec::new(),
+                guardian_vetoes: Vec::new(),
+                vetoed: false,
             };
 
             self.proposals.insert(proposal_id, &proposal);
@@ -155,20 +705,31 @@ mod devkit_showcase {
             Self::env().emit_event(ProposalCreated {
                 proposal_id,
                 proposer: caller,
+                track_id,
+                kind,
                 description,
             });
 
+            Self::env().emit_event(ProposalStateChanged {
+                proposal_id,
+                state: ProposalState::Active,
+            });
+
             Ok(proposal_id)
         }
 
         /// Votar en una propuesta
         #[ink(message)]
         pub fn vote(&mut self, proposal_id: u32, support: bool) -> Result<(), Error> {
+            self.non_reentrant(|contract| contract.vote_impl(proposal_id, support))
+        }
+
+        fn vote_impl(&mut self, proposal_id: u32, support: bool) -> Result<(), Error> {
             let caller = Self::env().caller();
             let mut proposal = self.proposals.get(proposal_id)
                 .ok_or(Error::ProposalNotFound)?;
 
-            let now = Self::env().block_timestamp();
+            let now = self.now();
             if now > proposal.end_time {
                 return Err(Error::VotingPeriodEnded);
             }
@@ -177,16 +738,13 @@ mod devkit_showcase {
                 return Err(Error::AlreadyVoted);
             }
 
-            // Obtener balance de tokens del votante (simplificado)
-            // En producción, consultar el contrato de tokens
-            let voter_balance = self.get_voter_balance(caller);
-
-            if support {
-                proposal.for_votes += voter_balance;
-            } else {
-                proposal.against_votes += voter_balance;
+            let voter_balance = self.get_voter_balance(caller, proposal.snapshot_block)?;
+            if voter_balance == 0 {
+                return Err(Error::NoVotingPower);
             }
 
+            Self::add_votes(&mut proposal, support, voter_balance)?;
+
             proposal.voters.push(caller);
             self.proposals.insert(proposal_id, &proposal);
 
@@ -200,9 +758,128 @@ mod devkit_showcase {
             Ok(())
         }
 
+        /// Votar en una propuesta con convicción (estilo OpenGov)
+        ///
+        /// El peso del voto es `balance` multiplicado por la convicción, y el
+        /// stake del votante queda bloqueado hasta el fin de la votación más
+        /// el período de votación del track por el número de períodos de la
+        /// convicción. `balance` no puede superar el poder de voto del
+        /// votante en el snapshot de la propuesta.
+        #[ink(message)]
+        pub fn vote_with_conviction(
+            &mut self,
+            proposal_id: u32,
+            support: bool,
+            balance: Balance,
+            conviction: Conviction,
+        ) -> Result<(), Error> {
+            self.non_reentrant(|contract| {
+                contract.vote_with_conviction_impl(proposal_id, support, balance, conviction)
+            })
+        }
+
+        fn vote_with_conviction_impl(
+            &mut self,
+            proposal_id: u32,
+            support: bool,
+            balance: Balance,
+            conviction: Conviction,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+
+            let now = self.now();
+            if now > proposal.end_time {
+                return Err(Error::VotingPeriodEnded);
+            }
+
+            if proposal.voters.contains(&caller) {
+                return Err(Error::AlreadyVoted);
+            }
+
+            if balance == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            let staker_info = self.stakers.get(caller)
+                .ok_or(Error::NoStake)?;
+            if staker_info.amount < balance {
+                return Err(Error::InsufficientStake);
+            }
+
+            // El stake no mueve tokens: el peso sale del token de gobernanza
+            let voting_power = self.get_voter_balance(caller, proposal.snapshot_block)?;
+            if balance > voting_power {
+                return Err(Error::InsufficientVotingPower);
+            }
+
+            let votes = conviction.votes(balance)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            Self::add_votes(&mut proposal, support, votes)?;
+
+            proposal.voters.push(caller);
+            self.proposals.insert(proposal_id, &proposal);
+
+            // Los bloqueos se superponen: se conserva el mayor monto y el
+            // vencimiento más lejano
+            let voting_period = proposal.end_time
+                .checked_sub(proposal.start_time)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let unlock_at = voting_period
+                .checked_mul(conviction.lock_periods())
+                .and_then(|x| x.checked_add(proposal.end_time))
+                .ok_or(Error::ArithmeticOverflow)?;
+            let mut lock = self.vote_locks.get(caller).unwrap_or(VoteLock {
+                amount: 0,
+                unlock_at: 0,
+            });
+            lock.amount = lock.amount.max(balance);
+            lock.unlock_at = lock.unlock_at.max(unlock_at);
+            self.vote_locks.insert(caller, &lock);
+
+            Self::env().emit_event(VotedWithConviction {
+                proposal_id,
+                voter: caller,
+                support,
+                balance,
+                conviction,
+                votes,
+                unlock_at: lock.unlock_at,
+            });
+
+            Ok(())
+        }
+
+        /// Liberar el bloqueo de stake una vez vencido
+        #[ink(message)]
+        pub fn unlock(&mut self) -> Result<Balance, Error> {
+            let staker = Self::env().caller();
+            let lock = self.vote_locks.get(staker)
+                .ok_or(Error::NoLock)?;
+
+            if self.now() < lock.unlock_at {
+                return Err(Error::LockNotExpired);
+            }
+
+            self.vote_locks.remove(staker);
+
+            Self::env().emit_event(Unlocked {
+                staker,
+                amount: lock.amount,
+            });
+
+            Ok(lock.amount)
+        }
+
         /// Ejecutar una propuesta aprobada
         #[ink(message)]
         pub fn execute(&mut self, proposal_id: u32) -> Result<(), Error> {
+            self.non_reentrant(|contract| contract.execute_impl(proposal_id))
+        }
+
+        fn execute_impl(&mut self, proposal_id: u32) -> Result<(), Error> {
             let mut proposal = self.proposals.get(proposal_id)
                 .ok_or(Error::ProposalNotFound)?;
 
@@ -210,52 +887,103 @@ mod devkit_showcase {
                 return Err(Error::AlreadyExecuted);
             }
 
-            let now = Self::env().block_timestamp();
+            if proposal.vetoed {
+                return Err(Error::ProposalVetoed);
+            }
+
+            let track = self.tracks.get(proposal.track_id)
+                .ok_or(Error::TrackNotFound)?;
+
+            let now = self.now();
             if now <= proposal.end_time {
                 return Err(Error::VotingPeriodActive);
             }
 
-            let total_votes = proposal.for_votes + proposal.against_votes;
-            if total_votes < self.quorum_threshold {
-                return Err(Error::QuorumNotMet);
+            let executable_at = proposal.end_time
+                .checked_add(track.timelock)
+                .ok_or(Error::ArithmeticOverflow)?;
+            if now <= executable_at {
+                return Err(Error::TimelockActive);
             }
 
-            if proposal.for_votes <= proposal.against_votes {
-                return Err(Error::ProposalRejected);
+            match proposal.kind {
+                ProposalKind::Majority => {
+                    let total_votes = proposal.for_votes
+                        .checked_add(proposal.against_votes)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    if total_votes < track.quorum_threshold {
+                        return Err(Error::QuorumNotMet);
+                    }
+
+                    let approval = proposal.for_votes
+                        .checked_mul(10_000)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    let required = total_votes
+                        .checked_mul(Balance::from(track.approval_threshold))
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    if approval <= required {
+                        return Err(Error::ProposalRejected);
+                    }
+                }
+                ProposalKind::Optimistic => {
+                    // El track pudo cambiar durante la votación
+                    Self::ensure_optimistic_allowed(&track, &proposal.action)?;
+                    if proposal.against_votes > track.veto_threshold {
+                        return Err(Error::ProposalRejected);
+                    }
+                }
             }
 
             proposal.executed = true;
             self.proposals.insert(proposal_id, &proposal);
 
+            if let Some(action) = proposal.action {
+                self.apply_action(proposal_id, action)?;
+            }
+
             Self::env().emit_event(ProposalExecuted {
                 proposal_id,
             });
 
+            Self::env().emit_event(ProposalStateChanged {
+                proposal_id,
+                state: ProposalState::Executed,
+            });
+
             Ok(())
         }
 
         /// Hacer stake de tokens
         #[ink(message)]
         pub fn stake(&mut self, amount: Balance) -> Result<(), Error> {
+            self.non_reentrant(|contract| contract.stake_impl(amount))
+        }
+
+        fn stake_impl(&mut self, amount: Balance) -> Result<(), Error> {
             let staker = Self::env().caller();
             
             if amount == 0 {
                 return Err(Error::InvalidAmount);
             }
 
-            self.update_rewards(staker);
+            self.update_rewards(staker)?;
 
             let mut staker_info = self.stakers.get(staker).unwrap_or(StakerInfo {
                 amount: 0,
                 reward_debt: 0,
-                last_update: Self::env().block_timestamp(),
+                reward_per_token_paid: self.reward_per_token_stored,
+                last_update: self.now(),
             });
 
-            staker_info.amount += amount;
-            staker_info.last_update = Self::env().block_timestamp();
-            self.stakers.insert(staker, &staker_info);
+            staker_info.amount = staker_info.amount
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            staker_info.last_update = self.now();
 
-            self.staking_pool += amount;
+            self.staking_pool = self.staking_pool
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.stakers.insert(staker, &staker_info);
 
             Self::env().emit_event(Staked {
                 staker,
@@ -268,21 +996,42 @@ mod devkit_showcase {
         /// Retirar stake
         #[ink(message)]
         pub fn unstake(&mut self, amount: Balance) -> Result<(), Error> {
+            self.non_reentrant(|contract| contract.unstake_impl(amount))
+        }
+
+        fn unstake_impl(&mut self, amount: Balance) -> Result<(), Error> {
             let staker = Self::env().caller();
+            // Un overflow en los acumuladores no debe congelar el principal
+            match self.update_rewards(staker) {
+                Err(Error::ArithmeticOverflow) => self.forfeit_rewards(staker),
+                result => result?,
+            }
+
             let mut staker_info = self.stakers.get(staker)
                 .ok_or(Error::NoStake)?;
 
-            if staker_info.amount < amount {
-                return Err(Error::InsufficientStake);
+            let remaining = staker_info.amount
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientStake)?;
+
+            if let Some(lock) = self.vote_locks.get(staker) {
+                if remaining < lock.amount {
+                    return Err(Error::StakeLocked);
+                }
             }
 
-            self.update_rewards(staker);
+            staker_info.amount = remaining;
+            staker_info.last_update = self.now();
 
-            staker_info.amount -= amount;
-            staker_info.last_update = Self::env().block_timestamp();
+            self.staking_pool = self.staking_pool
+                .checked_sub(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
             self.stakers.insert(staker, &staker_info);
 
-            self.staking_pool -= amount;
+            Self::env().emit_event(Unstaked {
+                staker,
+                amount,
+            });
 
             Ok(())
         }
@@ -290,8 +1039,12 @@ mod devkit_showcase {
         /// Reclamar recompensas
         #[ink(message)]
         pub fn claim_rewards(&mut self) -> Result<Balance, Error> {
+            self.non_reentrant(|contract| contract.claim_rewards_impl())
+        }
+
+        fn claim_rewards_impl(&mut self) -> Result<Balance, Error> {
             let staker = Self::env().caller();
-            self.update_rewards(staker);
+            self.update_rewards(staker)?;
 
             let mut staker_info = self.stakers.get(staker)
                 .ok_or(Error::NoStake)?;
@@ -302,7 +1055,7 @@ mod devkit_showcase {
             }
 
             staker_info.reward_debt = 0;
-            staker_info.last_update = Self::env().block_timestamp();
+            staker_info.last_update = self.now();
             self.stakers.insert(staker, &staker_info);
 
             Self::env().emit_event(RewardsClaimed {
@@ -313,60 +1066,811 @@ mod devkit_showcase {
             Ok(rewards)
         }
 
-        /// Obtener información de una propuesta
+        /// Obtener la unidad de tiempo del contrato
         #[ink(message)]
-        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
-            self.proposals.get(proposal_id)
+        pub fn clock_mode(&self) -> ClockMode {
+            self.clock_mode
         }
 
-        /// Obtener información de staking
+        /// Registrar un token PSP22 de recompensa (solo admin)
         #[ink(message)]
-        pub fn get_staker_info(&self, staker: AccountId) -> Option<StakerInfo> {
+        pub fn add_reward_token(&mut self, token: AccountId, rate: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            if self.reward_tokens.contains(&token) {
+                return Err(Error::RewardTokenExists);
+            }
+
+            if self.reward_tokens.len() >= MAX_REWARD_TOKENS {
+                return Err(Error::TooManyRewardTokens);
+            }
+
+            self.reward_tokens.push(token);
+            self.reward_token_info.insert(token, &RewardToken {
+                rate,
+                reward_per_token_stored: 0,
+                reserve: 0,
+                last_update: self.now(),
+            });
+
+            Self::env().emit_event(RewardTokenAdded {
+                token,
+                rate,
+            });
+
+            Ok(())
+        }
+
+        /// Cambiar la tasa de un token de recompensa (solo admin)
+        #[ink(message)]
+        pub fn set_reward_token_rate(&mut self, token: AccountId, rate: Balance) -> Result<(), Error> {
+            self.ensure_admin()?;
+
+            // Lo devengado hasta ahora se acumula con la tasa anterior
+            let mut info = self.accrue_token(token)?;
+            let old_rate = info.rate;
+            info.rate = rate;
+            self.reward_token_info.insert(token, &info);
+
+            Self::env().emit_event(RewardTokenRateChanged {
+                token,
+                old_rate,
+                new_rate: rate,
+            });
+
+            Ok(())
+        }
+
+        /// Depositar tokens de recompensa en la reserva
+        ///
+        /// El caller debe haber aprobado `amount` al contrato en el token.
+        #[ink(message)]
+        pub fn fund_reward_token(&mut self, token: AccountId, amount: Balance) -> Result<(), Error> {
+            self.non_reentrant(|contract| contract.fund_reward_token_impl(token, amount))
+        }
+
+        fn fund_reward_token_impl(&mut self, token: AccountId, amount: Balance) -> Result<(), Error> {
+            let funder = Self::env().caller();
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            let mut info = self.accrue_token(token)?;
+            info.reserve = info.reserve
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_token_info.insert(token, &info);
+
+            self.psp22_transfer_from(token, funder, Self::env().account_id(), amount)?;
+
+            Self::env().emit_event(RewardTokenFunded {
+                token,
+                funder,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Reclamar las recompensas de un token PSP22
+        ///
+        /// Es el `claim_rewards(token)` del diseño multi-token: ink! no admite
+        /// sobrecarga de mensajes y `claim_rewards()` ya reclama la recompensa
+        /// nativa, así que se conserva ese selector y este lleva otro nombre.
+        #[ink(message)]
+        pub fn claim_token_rewards(&mut self, token: AccountId) -> Result<Balance, Error> {
+            self.non_reentrant(|contract| contract.claim_token_rewards_impl(token))
+        }
+
+        fn claim_token_rewards_impl(&mut self, token: AccountId) -> Result<Balance, Error> {
+            let staker = Self::env().caller();
+            if !self.reward_tokens.contains(&token) {
+                return Err(Error::RewardTokenNotFound);
+            }
+
+            self.update_rewards(staker)?;
+
+            let amount = self.take_token_rewards(token, staker)?;
+            if amount == 0 {
+                return Err(Error::NoRewards);
+            }
+
+            Ok(amount)
+        }
+
+        /// Reclamar las recompensas de todos los tokens PSP22
+        #[ink(message)]
+        pub fn claim_all_rewards(&mut self) -> Result<Vec<(AccountId, Balance)>, Error> {
+            self.non_reentrant(|contract| contract.claim_all_rewards_impl())
+        }
+
+        fn claim_all_rewards_impl(&mut self) -> Result<Vec<(AccountId, Balance)>, Error> {
+            let staker = Self::env().caller();
+            self.update_rewards(staker)?;
+
+            let mut claimed = Vec::new();
+            for token in self.reward_tokens.clone() {
+                let amount = self.take_token_rewards(token, staker)?;
+                if amount > 0 {
+                    claimed.push((token, amount));
+                }
+            }
+
+            if claimed.is_empty() {
+                return Err(Error::NoRewards);
+            }
+
+            Ok(claimed)
+        }
+
+        /// Obtener las recompensas pendientes de una cuenta por token
+        ///
+        /// Simula los acumuladores hasta el instante actual sin modificar el
+        /// estado, así que incluye lo devengado desde la última liquidación.
+        /// Los tokens cuya simulación desborda se omiten; su error se obtiene
+        /// con `get_pending_token_rewards`.
+        #[ink(message)]
+        pub fn get_pending_rewards(&self, account: AccountId) -> Vec<(AccountId, Balance)> {
+            self.reward_tokens.iter()
+                .filter_map(|token| {
+                    let owed = self.get_pending_token_rewards(account, *token).ok()?;
+                    Some((*token, owed))
+                })
+                .collect()
+        }
+
+        /// Obtener las recompensas pendientes de una cuenta en un token
+        #[ink(message)]
+        pub fn get_pending_token_rewards(&self, account: AccountId, token: AccountId) -> Result<Balance, Error> {
+            let info = self.reward_token_info.get(token)
+                .ok_or(Error::RewardTokenNotFound)?;
+            let amount = self.stakers.get(account)
+                .map(|staker_info| staker_info.amount)
+                .unwrap_or(0);
+
+            self.simulate_pending_token(info, token, account, amount)
+        }
+
+        /// Obtener las recompensas de staking pendientes de una cuenta
+        ///
+        /// A diferencia de `reward_debt` en `get_staker_info`, incluye lo
+        /// devengado desde la última liquidación.
+        #[ink(message)]
+        pub fn get_pending_staking_rewards(&self, account: AccountId) -> Result<Balance, Error> {
+            let Some(staker_info) = self.stakers.get(account) else {
+                return Ok(0);
+            };
+
+            Self::earned(
+                staker_info.amount,
+                self.simulate_reward_per_token()?,
+                staker_info.reward_per_token_paid,
+            )?
+            .checked_add(staker_info.reward_debt)
+            .ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Obtener el APR de staking en basis points
+        ///
+        /// Anualiza `reward_rate` según el reloj (bloques de 6 segundos en modo
+        /// `BlockNumber`) y lo divide por el pool, asumiendo que la recompensa
+        /// y el token staked tienen el mismo valor. Devuelve 0 si el pool está
+        /// vacío.
+        #[ink(message)]
+        pub fn get_apr(&self) -> Result<Balance, Error> {
+            if self.staking_pool == 0 {
+                return Ok(0);
+            }
+
+            let units_per_year = match self.clock_mode {
+                ClockMode::BlockNumber => BLOCKS_PER_YEAR,
+                ClockMode::Timestamp => MILLIS_PER_YEAR,
+            };
+
+            self.reward_rate
+                .checked_mul(units_per_year)
+                .and_then(|x| x.checked_mul(10_000))
+                .map(|x| x / self.staking_pool)
+                .ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Obtener los tokens de recompensa registrados
+        #[ink(message)]
+        pub fn get_reward_tokens(&self) -> Vec<(AccountId, RewardToken)> {
+            self.reward_tokens
+                .iter()
+                .filter_map(|token| self.reward_token_info.get(token).map(|info| (*token, info)))
+                .collect()
+        }
+
+        /// Obtener el administrador
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
+        /// Obtener información de una propuesta
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Obtener la configuración de un track
+        #[ink(message)]
+        pub fn get_track(&self, track_id: u16) -> Option<Track> {
+            self.tracks.get(track_id)
+        }
+
+        /// Obtener todos los tracks configurados
+        #[ink(message)]
+        pub fn get_tracks(&self) -> Vec<(u16, Track)> {
+            self.track_ids
+                .iter()
+                .filter_map(|id| self.tracks.get(id).map(|track| (*id, track)))
+                .collect()
+        }
+
+        /// Obtener el consejo de guardianes y su umbral
+        #[ink(message)]
+        pub fn get_guardians(&self) -> (Vec<AccountId>, u32) {
+            (self.guardians.clone(), self.guardian_threshold)
+        }
+
+        /// Obtener información de staking
+        #[ink(message)]
+        pub fn get_staker_info(&self, staker: AccountId) -> Option<StakerInfo> {
             self.stakers.get(staker)
         }
 
+        /// Obtener el bloqueo por convicción de una cuenta
+        #[ink(message)]
+        pub fn get_vote_lock(&self, staker: AccountId) -> Option<VoteLock> {
+            self.vote_locks.get(staker)
+        }
+
+        /// Obtener fondos del tesoro
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            self.treasury_balance
+        }
+
         /// Obtener balance del pool de staking
         #[ink(message)]
         pub fn get_staking_pool(&self) -> Balance {
             self.staking_pool
         }
 
+        /// Aplicar la acción de una propuesta aprobada
+        fn apply_action(&mut self, proposal_id: u32, action: ProposalAction) -> Result<(), Error> {
+            match action {
+                ProposalAction::SetTrack { track_id, track } => {
+                    Self::validate_track(&track)?;
+
+                    if !self.track_ids.contains(&track_id) {
+                        self.track_ids.push(track_id);
+                    }
+                    self.tracks.insert(track_id, &track);
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::Track { track_id, track },
+                    });
+                }
+                ProposalAction::Slash { account, basis_points, reason, destination } => {
+                    self.slash(proposal_id, account, basis_points, reason, destination)?;
+                }
+                ProposalAction::SetGuardians { guardians, threshold } => {
+                    Self::validate_guardians(&guardians, threshold)?;
+
+                    self.guardians = guardians.clone();
+                    self.guardian_threshold = threshold;
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::Guardians { guardians, threshold },
+                    });
+                }
+                ProposalAction::SetRewardRate { reward_rate } => {
+                    // Lo devengado hasta ahora se acumula con la tasa anterior.
+                    // Si ya no cabe en el acumulador se pierde, para que la
+                    // nueva tasa pueda sacar al contrato del desborde.
+                    match self.accrue_rewards() {
+                        Err(Error::ArithmeticOverflow) => {
+                            self.last_reward_update = self.now();
+                        }
+                        result => result?,
+                    }
+
+                    let old_rate = self.reward_rate;
+                    self.reward_rate = reward_rate;
+
+                    Self::env().emit_event(RewardRateChanged {
+                        proposal_id,
+                        old_rate,
+                        new_rate: reward_rate,
+                    });
+                }
+                ProposalAction::AcceptOracleOwnership { oracle } => {
+                    let mut oracle_ref: HyperbridgeOracleRef = FromAccountId::from_account_id(oracle);
+                    oracle_ref
+                        .call_mut()
+                        .accept_ownership()
+                        .try_invoke()
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?;
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::OracleOwner { oracle },
+                    });
+                }
+                ProposalAction::AddOracleValidator { oracle, validator } => {
+                    let mut oracle_ref: HyperbridgeOracleRef = FromAccountId::from_account_id(oracle);
+                    oracle_ref
+                        .call_mut()
+                        .add_validator(validator)
+                        .try_invoke()
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?;
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::OracleValidator { oracle, validator },
+                    });
+                }
+                ProposalAction::SetArkivAddress { storage, arkiv_address } => {
+                    let mut storage_ref: ArkivStorageRef = FromAccountId::from_account_id(storage);
+                    storage_ref
+                        .call_mut()
+                        .set_arkiv_address(arkiv_address.clone())
+                        .try_invoke()
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?;
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::ArkivAddress { storage, arkiv_address },
+                    });
+                }
+                ProposalAction::AcceptArkivOwnership { storage } => {
+                    let mut storage_ref: ArkivStorageRef = FromAccountId::from_account_id(storage);
+                    storage_ref
+                        .call_mut()
+                        .accept_ownership()
+                        .try_invoke()
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?
+                        .map_err(|_| Error::CrossContractCallFailed)?;
+
+                    Self::env().emit_event(ParameterChanged {
+                        proposal_id,
+                        parameter: Parameter::ArkivOwner { storage },
+                    });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Validar los parámetros de un track
+        fn validate_track(track: &Track) -> Result<(), Error> {
+            if track.voting_period == 0 || track.approval_threshold > 10_000 {
+                return Err(Error::InvalidTrack);
+            }
+            Ok(())
+        }
+
+        /// Validar que una propuesta optimista está permitida en el track
+        fn ensure_optimistic_allowed(track: &Track, action: &Option<ProposalAction>) -> Result<(), Error> {
+            if !track.optimistic_allowed {
+                return Err(Error::OptimisticNotAllowed);
+            }
+            if action.as_ref().is_some_and(|action| !action.is_low_risk()) {
+                return Err(Error::ActionRequiresMajority);
+            }
+            Ok(())
+        }
+
+        /// Validar el consejo de guardianes (vacío lo desactiva)
+        fn validate_guardians(guardians: &[AccountId], threshold: u32) -> Result<(), Error> {
+            let valid = if guardians.is_empty() {
+                threshold == 0
+            } else {
+                threshold > 0 && threshold as usize <= guardians.len()
+            };
+
+            if !valid {
+                return Err(Error::InvalidGuardians);
+            }
+            Ok(())
+        }
+
+        /// Recortar el stake de una cuenta (solo vía propuesta aprobada)
+        ///
+        /// Las recompensas de la cuenta se liquidan antes del recorte para que
+        /// el acumulador global no cambie y el resto de stakers no se vea afectado.
+        fn slash(
+            &mut self,
+            proposal_id: u32,
+            account: AccountId,
+            basis_points: u16,
+            reason: String,
+            destination: SlashDestination,
+        ) -> Result<(), Error> {
+            if basis_points == 0 || basis_points > 10_000 {
+                return Err(Error::InvalidSlash);
+            }
+
+            self.update_rewards(account)?;
+
+            let mut staker_info = self.stakers.get(account)
+                .ok_or(Error::NoStake)?;
+
+            let amount = staker_info.amount
+                .checked_mul(Balance::from(basis_points))
+                .ok_or(Error::ArithmeticOverflow)?
+                / 10_000;
+
+            staker_info.amount = staker_info.amount
+                .checked_sub(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            staker_info.last_update = self.now();
+
+            self.staking_pool = self.staking_pool
+                .checked_sub(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.stakers.insert(account, &staker_info);
+
+            // El bloqueo por convicción no puede superar el stake restante
+            if let Some(mut lock) = self.vote_locks.get(account) {
+                if lock.amount > staker_info.amount {
+                    lock.amount = staker_info.amount;
+                    self.vote_locks.insert(account, &lock);
+                }
+            }
+
+            if destination == SlashDestination::Treasury {
+                self.treasury_balance = self.treasury_balance
+                    .checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+
+            Self::env().emit_event(Slashed {
+                proposal_id,
+                account,
+                amount,
+                reason,
+                destination,
+            });
+
+            Ok(())
+        }
+
+        /// Instante actual según el reloj del contrato
+        fn now(&self) -> u64 {
+            Self::clock(self.clock_mode)
+        }
+
+        /// Leer el reloj de la chain en la unidad indicada
+        fn clock(mode: ClockMode) -> u64 {
+            match mode {
+                ClockMode::BlockNumber => u64::from(Self::env().block_number()),
+                ClockMode::Timestamp => Self::env().block_timestamp(),
+            }
+        }
+
+        /// Sumar votos a favor o en contra de una propuesta
+        fn add_votes(proposal: &mut Proposal, support: bool, votes: Balance) -> Result<(), Error> {
+            let tally = if support {
+                &mut proposal.for_votes
+            } else {
+                &mut proposal.against_votes
+            };
+            *tally = tally
+                .checked_add(votes)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
         /// Actualizar recompensas de un staker
-        fn update_rewards(&mut self, staker: AccountId) {
-            let current_time = Self::env().block_timestamp();
-            let time_elapsed = current_time.saturating_sub(self.last_reward_update);
+        ///
+        /// Primero avanza el acumulador global de recompensas por token y
+        /// luego liquida lo devengado por `staker` desde su última liquidación.
+        /// Un overflow devuelve `Error::ArithmeticOverflow` en lugar de
+        /// descartar recompensas.
+        fn update_rewards(&mut self, staker: AccountId) -> Result<(), Error> {
+            self.accrue_rewards()?;
+
+            for token in self.reward_tokens.clone() {
+                self.settle_token(token, staker)?;
+            }
+
+            if let Some(mut staker_info) = self.stakers.get(staker) {
+                let staker_share = Self::earned(
+                    staker_info.amount,
+                    self.reward_per_token_stored,
+                    staker_info.reward_per_token_paid,
+                )?;
+
+                staker_info.reward_debt = staker_info.reward_debt
+                    .checked_add(staker_share)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                staker_info.reward_per_token_paid = self.reward_per_token_stored;
+                staker_info.last_update = self.now();
+                self.stakers.insert(staker, &staker_info);
+            }
+
+            Ok(())
+        }
+
+        /// Avanzar el acumulador de un token de recompensa y guardarlo
+        fn accrue_token(&mut self, token: AccountId) -> Result<RewardToken, Error> {
+            let info = self.reward_token_info.get(token)
+                .ok_or(Error::RewardTokenNotFound)?;
+            let info = self.simulate_token(info)?;
+            self.reward_token_info.insert(token, &info);
+
+            Ok(info)
+        }
+
+        /// Estado de un token de recompensa avanzado hasta el instante actual,
+        /// sin escribir en storage
+        ///
+        /// Solo se reparte lo que hay en la reserva, así las recompensas
+        /// asignadas siempre están respaldadas por fondos depositados.
+        fn simulate_token(&self, mut info: RewardToken) -> Result<RewardToken, Error> {
+            let current_time = self.now();
+            let time_elapsed = current_time.saturating_sub(info.last_update);
 
             if time_elapsed > 0 && self.staking_pool > 0 {
-                let total_rewards = self.reward_rate
-                    .checked_mul(time_elapsed)
-                    .unwrap_or(0);
-
-                if let Some(mut staker_info) = self.stakers.get(staker) {
-                    if staker_info.amount > 0 {
-                        let staker_share = (staker_info.amount
-                            .checked_mul(total_rewards))
-                            .and_then(|x| x.checked_div(self.staking_pool))
-                            .unwrap_or(0);
-
-                        staker_info.reward_debt = staker_info.reward_debt
-                            .checked_add(staker_share)
-                            .unwrap_or(staker_info.reward_debt);
-                    }
+                let emitted = info.rate
+                    .checked_mul(Balance::from(time_elapsed))
+                    .ok_or(Error::ArithmeticOverflow)?
+                    .min(info.reserve);
+                let increment = emitted
+                    .checked_mul(REWARD_PRECISION)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / self.staking_pool;
+
+                info.reward_per_token_stored = info.reward_per_token_stored
+                    .checked_add(increment)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                info.reserve -= emitted;
+            }
+
+            info.last_update = current_time;
+            Ok(info)
+        }
+
+        /// Recompensas pendientes de `staker` en un token, sin escribir en storage
+        fn simulate_pending_token(
+            &self,
+            info: RewardToken,
+            token: AccountId,
+            staker: AccountId,
+            amount: Balance,
+        ) -> Result<Balance, Error> {
+            let state = self.token_rewards.get((token, staker)).unwrap_or_default();
+            if amount == 0 {
+                return Ok(state.owed);
+            }
+
+            let info = self.simulate_token(info)?;
+            Self::earned(amount, info.reward_per_token_stored, state.reward_per_token_paid)?
+                .checked_add(state.owed)
+                .ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Liquidar las recompensas de `staker` en un token
+        fn settle_token(&mut self, token: AccountId, staker: AccountId) -> Result<(), Error> {
+            let info = self.accrue_token(token)?;
+            let amount = self.stakers.get(staker)
+                .map(|staker_info| staker_info.amount)
+                .unwrap_or(0);
+
+            let mut state = self.token_rewards.get((token, staker)).unwrap_or_default();
+            let share = Self::earned(
+                amount,
+                info.reward_per_token_stored,
+                state.reward_per_token_paid,
+            )?;
+
+            state.owed = state.owed
+                .checked_add(share)
+                .ok_or(Error::ArithmeticOverflow)?;
+            state.reward_per_token_paid = info.reward_per_token_stored;
+            self.token_rewards.insert((token, staker), &state);
 
-                    staker_info.last_update = current_time;
-                    self.stakers.insert(staker, &staker_info);
+            Ok(())
+        }
+
+        /// Cerrar la liquidación de `staker` sin devengar lo pendiente
+        ///
+        /// Solo para cuando un acumulador desborda: se pierde lo no liquidado
+        /// desde la última actualización, pero el stake puede retirarse.
+        fn forfeit_rewards(&mut self, staker: AccountId) {
+            for token in self.reward_tokens.clone() {
+                if let Some(info) = self.reward_token_info.get(token) {
+                    let mut state = self.token_rewards.get((token, staker)).unwrap_or_default();
+                    state.reward_per_token_paid = info.reward_per_token_stored;
+                    self.token_rewards.insert((token, staker), &state);
                 }
             }
 
-            self.last_reward_update = current_time;
+            if let Some(mut staker_info) = self.stakers.get(staker) {
+                staker_info.reward_per_token_paid = self.reward_per_token_stored;
+                self.stakers.insert(staker, &staker_info);
+            }
+        }
+
+        /// Poner a cero y transferir las recompensas liquidadas de un token
+        fn take_token_rewards(&mut self, token: AccountId, staker: AccountId) -> Result<Balance, Error> {
+            let mut state = self.token_rewards.get((token, staker)).unwrap_or_default();
+            let amount = state.owed;
+            if amount == 0 {
+                return Ok(0);
+            }
+
+            state.owed = 0;
+            self.token_rewards.insert((token, staker), &state);
+
+            self.psp22_transfer(token, staker, amount)?;
+
+            Self::env().emit_event(TokenRewardsClaimed {
+                token,
+                staker,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Llamar a `PSP22::transfer` en `token`
+        ///
+        /// Con los flags por defecto la reentrada queda denegada: el token no
+        /// puede ejecutar ningún mensaje de este contrato durante la llamada.
+        fn psp22_transfer(&self, token: AccountId, to: AccountId, value: Balance) -> Result<(), Error> {
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)
+        }
+
+        /// Llamar a `PSP22::transfer_from` en `token` (misma política de reentrada)
+        fn psp22_transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), Error> {
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)
+        }
+
+        /// Ejecutar `f` con el lock de reentrada tomado
+        ///
+        /// Cubre todos los mensajes que hacen llamadas externas; es una segunda
+        /// barrera por si alguna llamada saliente llegara a permitir reentrada,
+        /// en cuyo caso el intento recibe `Error::Reentrancy`.
+        fn non_reentrant<T>(
+            &mut self,
+            f: impl FnOnce(&mut Self) -> Result<T, Error>,
+        ) -> Result<T, Error> {
+            if self.reentrancy_lock.get().unwrap_or(false) {
+                return Err(Error::Reentrancy);
+            }
+
+            self.reentrancy_lock.set(&true);
+            let result = f(self);
+            self.reentrancy_lock.set(&false);
+
+            result
+        }
+
+        /// Verificar que el caller es el admin
+        fn ensure_admin(&self) -> Result<(), Error> {
+            if Self::env().caller() != self.admin {
+                return Err(Error::NotAdmin);
+            }
+            Ok(())
+        }
+
+        /// Avanzar el acumulador global de recompensas hasta el instante actual
+        ///
+        /// La recompensa de staking nativa no tiene reserva: `reward_rate` se
+        /// reparte entera y solo se contabiliza en `reward_debt`.
+        fn accrue_rewards(&mut self) -> Result<(), Error> {
+            self.reward_per_token_stored = self.simulate_reward_per_token()?;
+            self.last_reward_update = self.now();
+
+            Ok(())
+        }
+
+        /// Acumulador global avanzado hasta el instante actual, sin escribir
+        /// en storage
+        fn simulate_reward_per_token(&self) -> Result<Balance, Error> {
+            let time_elapsed = self.now().saturating_sub(self.last_reward_update);
+
+            if time_elapsed == 0 || self.staking_pool == 0 {
+                return Ok(self.reward_per_token_stored);
+            }
+
+            let increment = self.reward_rate
+                .checked_mul(Balance::from(time_elapsed))
+                .and_then(|x| x.checked_mul(REWARD_PRECISION))
+                .ok_or(Error::ArithmeticOverflow)?
+                / self.staking_pool;
+
+            self.reward_per_token_stored
+                .checked_add(increment)
+                .ok_or(Error::ArithmeticOverflow)
+        }
+
+        /// Recompensas devengadas por `amount` entre dos valores del acumulador
+        fn earned(amount: Balance, reward_per_token: Balance, paid: Balance) -> Result<Balance, Error> {
+            let pending_per_token = reward_per_token
+                .checked_sub(paid)
+                .ok_or(Error::ArithmeticOverflow)?;
+            amount
+                .checked_mul(pending_per_token)
+                .map(|x| x / REWARD_PRECISION)
+                .ok_or(Error::ArithmeticOverflow)
         }
 
-        /// Obtener balance de tokens del votante (simplificado)
-        /// En producción, consultar el contrato de tokens
-        fn get_voter_balance(&self, voter: AccountId) -> Balance {
-            // Simplificado: retornar balance fijo
-            // En producción, hacer cross-contract call al governance_token
-            1000
+        /// Poder de voto delegado al votante en `governance_token` al
+        /// cierre del bloque de snapshot de la propuesta
+        #[cfg(not(test))]
+        fn get_voter_balance(
+            &self,
+            voter: AccountId,
+            snapshot_block: BlockNumber,
+        ) -> Result<Balance, Error> {
+            let token: GovernanceTokenRef = FromAccountId::from_account_id(self.governance_token);
+            token
+                .call()
+                .get_past_votes(voter, snapshot_block)
+                .try_invoke()
+                .map_err(|_| Error::CrossContractCallFailed)?
+                .map_err(|_| Error::CrossContractCallFailed)?
+                .map_err(|_| Error::CrossContractCallFailed)
+        }
+
+        /// Off-chain no hay llamadas entre contratos: se consulta el doble
+        /// del token registrado en `governance_token`
+        #[cfg(test)]
+        fn get_voter_balance(
+            &self,
+            voter: AccountId,
+            snapshot_block: BlockNumber,
+        ) -> Result<Balance, Error> {
+            mock_governance_token::get_past_votes(self.governance_token, voter, snapshot_block)
+                .map_err(|_| Error::CrossContractCallFailed)
         }
     }
 
@@ -385,6 +1889,1974 @@ mod devkit_showcase {
         NoStake,
         InsufficientStake,
         NoRewards,
+        StakeLocked,
+        NoLock,
+        LockNotExpired,
+        TrackNotFound,
+        InvalidTrack,
+        InsufficientProposerStake,
+        TimelockActive,
+        InvalidSlash,
+        ArithmeticOverflow,
+        NotGuardian,
+        AlreadyVetoed,
+        ProposalVetoed,
+        InvalidGuardians,
+        NotAdmin,
+        RewardTokenExists,
+        RewardTokenNotFound,
+        TooManyRewardTokens,
+        TokenTransferFailed,
+        InvalidAction,
+        CrossContractCallFailed,
+        Reentrancy,
+        OptimisticNotAllowed,
+        ActionRequiresMajority,
+        NoVotingPower,
+        InsufficientVotingPower,
     }
-}
 
+    /// Decodificador de eventos para indexers off-chain (solo `std`)
+    ///
+    /// Identifica el evento por su signature topic (el primer topic que
+    /// emite ink!) y decodifica los datos SCALE al struct correspondiente.
+    #[cfg(feature = "std")]
+    pub mod indexer {
+        use super::*;
+        use ink::env::Event as _;
+        use scale::Decode as _;
+
+        /// Error al decodificar un evento
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum DecodeError {
+            /// El evento no trae signature topic
+            MissingSignatureTopic,
+            /// El signature topic no corresponde a ningún evento del contrato
+            UnknownEvent([u8; 32]),
+            /// Los datos no coinciden con el evento
+            InvalidData(scale::Error),
+        }
+
+        macro_rules! contract_events {
+            ($($event:ident),* $(,)?) => {
+                /// Cualquier evento emitido por `DevKitShowcase`
+                pub enum ContractEvent {
+                    $($event($event),)*
+                }
+
+                /// Decodificar un evento a partir de sus topics y datos crudos
+                pub fn decode_event(
+                    topics: &[[u8; 32]],
+                    data: &[u8],
+                ) -> Result<ContractEvent, DecodeError> {
+                    let signature = topics.first().ok_or(DecodeError::MissingSignatureTopic)?;
+                    let mut input = data;
+                    $(
+                        if <$event>::SIGNATURE_TOPIC.as_ref() == Some(signature) {
+                            return <$event>::decode(&mut input)
+                                .map(ContractEvent::$event)
+                                .map_err(DecodeError::InvalidData);
+                        }
+                    )*
+                    Err(DecodeError::UnknownEvent(*signature))
+                }
+            };
+        }
+
+        contract_events!(
+            ProposalCreated,
+            ProposalStateChanged,
+            Voted,
+            VotedWithConviction,
+            GuardianVetoCast,
+            ProposalExecuted,
+            ParameterChanged,
+            RewardRateChanged,
+            Staked,
+            Unstaked,
+            Unlocked,
+            Slashed,
+            RewardsClaimed,
+            RewardTokenAdded,
+            RewardTokenRateChanged,
+            RewardTokenFunded,
+            TokenRewardsClaimed,
+        );
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use ink::env::test;
+            use ink::env::DefaultEnvironment;
+
+            fn decode_recorded() -> Vec<ContractEvent> {
+                test::recorded_events()
+                    .map(|event| {
+                        let topics: Vec<[u8; 32]> = event
+                            .topics
+                            .iter()
+                            .map(|topic| topic.as_slice().try_into().unwrap())
+                            .collect();
+                        decode_event(&topics, &event.data).unwrap()
+                    })
+                    .collect()
+            }
+
+            #[ink::test]
+            fn decodes_staking_events() {
+                let alice = test::default_accounts::<DefaultEnvironment>().alice;
+                test::set_caller::<DefaultEnvironment>(alice);
+                let mut contract = DevKitShowcase::new(
+                    AccountId::from([0x01; 32]),
+                    100,
+                    1000,
+                    1,
+                    ClockMode::Timestamp,
+                );
+                contract.stake(100).unwrap();
+                contract.unstake(40).unwrap();
+
+                let events = decode_recorded();
+                assert_eq!(events.len(), 2);
+                assert!(matches!(
+                    &events[0],
+                    ContractEvent::Staked(Staked { staker, amount: 100 }) if *staker == alice
+                ));
+                assert!(matches!(
+                    &events[1],
+                    ContractEvent::Unstaked(Unstaked { staker, amount: 40 }) if *staker == alice
+                ));
+            }
+
+            #[ink::test]
+            fn decodes_proposal_lifecycle_and_parameter_changes() {
+                mock_governance_token::seed_default_accounts(AccountId::from([0x01; 32]), 1000);
+                test::set_block_timestamp::<DefaultEnvironment>(0);
+                let mut contract = DevKitShowcase::new(
+                    AccountId::from([0x01; 32]),
+                    100,
+                    1000,
+                    1,
+                    ClockMode::Timestamp,
+                );
+                let action = ProposalAction::SetRewardRate { reward_rate: 5 };
+                contract.propose(DEFAULT_TRACK, String::from("rate"), Some(action)).unwrap();
+                contract.vote(0, true).unwrap();
+                test::set_block_timestamp::<DefaultEnvironment>(101);
+                contract.execute(0).unwrap();
+
+                let events = decode_recorded();
+                assert!(matches!(&events[0], ContractEvent::ProposalCreated(_)));
+                assert!(matches!(
+                    &events[1],
+                    ContractEvent::ProposalStateChanged(ProposalStateChanged {
+                        proposal_id: 0,
+                        state: ProposalState::Active,
+                    })
+                ));
+                assert!(matches!(&events[2], ContractEvent::Voted(_)));
+                assert!(matches!(
+                    &events[3],
+                    ContractEvent::RewardRateChanged(RewardRateChanged {
+                        proposal_id: 0,
+                        old_rate: 1,
+                        new_rate: 5,
+                    })
+                ));
+                assert!(matches!(&events[4], ContractEvent::ProposalExecuted(_)));
+                assert!(matches!(
+                    &events[5],
+                    ContractEvent::ProposalStateChanged(ProposalStateChanged {
+                        state: ProposalState::Executed,
+                        ..
+                    })
+                ));
+            }
+
+            #[test]
+            fn rejects_unknown_signature() {
+                assert_eq!(
+                    decode_event(&[[0xff; 32]], &[]).err(),
+                    Some(DecodeError::UnknownEvent([0xff; 32]))
+                );
+                assert_eq!(decode_event(&[], &[]).err(), Some(DecodeError::MissingSignatureTopic));
+            }
+        }
+    }
+
+    /// Doble de `governance_token` para los tests off-chain
+    ///
+    /// Guarda checkpoints de votos por token y cuenta, y responde
+    /// `get_past_votes` como el token: `FutureLookup` desde el bloque actual
+    /// y cero votos sin checkpoint previo al bloque pedido.
+    #[cfg(test)]
+    mod mock_governance_token {
+        use super::{AccountId, Balance, BlockNumber};
+        use governance_token::VotesError;
+        use ink::env::{test, DefaultEnvironment};
+        use std::cell::RefCell;
+
+        thread_local! {
+            static CHECKPOINTS: RefCell<Vec<(AccountId, AccountId, BlockNumber, Balance)>> =
+                const { RefCell::new(Vec::new()) };
+        }
+
+        /// Fijar los votos de `account` en `token` desde el bloque actual
+        pub fn set_votes(token: AccountId, account: AccountId, votes: Balance) {
+            let block = ink::env::block_number::<DefaultEnvironment>();
+            CHECKPOINTS.with(|c| c.borrow_mut().push((token, account, block, votes)));
+        }
+
+        pub fn get_past_votes(
+            token: AccountId,
+            account: AccountId,
+            block: BlockNumber,
+        ) -> Result<Balance, VotesError> {
+            if block >= ink::env::block_number::<DefaultEnvironment>() {
+                return Err(VotesError::FutureLookup);
+            }
+            Ok(CHECKPOINTS.with(|c| {
+                c.borrow()
+                    .iter()
+                    .rev()
+                    .find(|(t, a, b, _)| *t == token && *a == account && *b <= block)
+                    .map_or(0, |(_, _, _, votes)| *votes)
+            }))
+        }
+
+        /// Dar `votes` a las cuentas por defecto y cerrar el bloque para que
+        /// las propuestas nuevas los tomen en su snapshot
+        pub fn seed_default_accounts(token: AccountId, votes: Balance) {
+            CHECKPOINTS.with(|c| c.borrow_mut().clear());
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            for account in [
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+                accounts.eve,
+                accounts.frank,
+            ] {
+                set_votes(token, account, votes);
+            }
+            test::advance_block::<DefaultEnvironment>();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+        use ink::env::DefaultEnvironment;
+
+        const VOTING_PERIOD: u64 = 100;
+        const QUORUM: Balance = 1000;
+        const REWARD_RATE: Balance = 10;
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        fn set_time(timestamp: u64) {
+            test::set_block_timestamp::<DefaultEnvironment>(timestamp);
+        }
+
+        fn emitted_events() -> usize {
+            test::recorded_events().count()
+        }
+
+        const TOKEN: [u8; 32] = [0x01; 32];
+        const VOTES: Balance = 1000;
+
+        fn contract() -> DevKitShowcase {
+            set_caller(accounts().alice);
+            mock_governance_token::seed_default_accounts(AccountId::from(TOKEN), VOTES);
+            set_time(0);
+            DevKitShowcase::new(
+                AccountId::from(TOKEN),
+                VOTING_PERIOD,
+                QUORUM,
+                REWARD_RATE,
+                ClockMode::Timestamp,
+            )
+        }
+
+        fn treasury_track() -> Track {
+            Track {
+                name: String::from("Treasury-small"),
+                voting_period: 50,
+                quorum_threshold: 500,
+                approval_threshold: 6000,
+                timelock: 20,
+                min_proposer_stake: 100,
+                veto_threshold: 500,
+                optimistic_allowed: false,
+            }
+        }
+
+        /// Crea, vota y ejecuta una propuesta en el track por defecto
+        fn pass_proposal(contract: &mut DevKitShowcase, action: ProposalAction) {
+            let start = ink::env::block_timestamp::<DefaultEnvironment>();
+            let proposal_id = contract
+                .propose(DEFAULT_TRACK, String::from("action"), Some(action))
+                .unwrap();
+            contract.vote(proposal_id, true).unwrap();
+            set_time(start + VOTING_PERIOD + 1);
+            contract.execute(proposal_id).unwrap();
+        }
+
+        #[ink::test]
+        fn new_creates_default_track() {
+            let contract = contract();
+            assert_eq!(contract.clock_mode(), ClockMode::Timestamp);
+            let track = contract.get_track(DEFAULT_TRACK).unwrap();
+            assert_eq!(track.voting_period, VOTING_PERIOD);
+            assert_eq!(track.quorum_threshold, QUORUM);
+            assert_eq!(track.approval_threshold, 5000);
+            assert_eq!(contract.get_tracks().len(), 1);
+            assert_eq!(contract.get_staking_pool(), 0);
+            assert_eq!(contract.get_treasury_balance(), 0);
+        }
+
+        #[ink::test]
+        fn block_number_clock_drives_periods() {
+            set_caller(accounts().alice);
+            mock_governance_token::seed_default_accounts(AccountId::from(TOKEN), VOTES);
+            let mut contract = DevKitShowcase::new(
+                AccountId::from([0x01; 32]),
+                3,
+                QUORUM,
+                REWARD_RATE,
+                ClockMode::BlockNumber,
+            );
+            assert_eq!(contract.clock_mode(), ClockMode::BlockNumber);
+
+            let start = u64::from(ink::env::block_number::<DefaultEnvironment>());
+            contract.stake(100).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote(0, true).unwrap();
+            assert_eq!(contract.get_proposal(0).unwrap().end_time, start + 3);
+
+            // El timestamp avanza pero solo cuentan los bloques
+            set_time(1_000_000);
+            assert_eq!(contract.execute(0), Err(Error::VotingPeriodActive));
+
+            for _ in 0..4 {
+                test::advance_block::<DefaultEnvironment>();
+            }
+            assert_eq!(contract.execute(0), Ok(()));
+            assert_eq!(contract.claim_rewards(), Ok(REWARD_RATE * 4));
+        }
+
+        #[ink::test]
+        fn propose_works() {
+            let mut contract = contract();
+            set_time(10);
+            assert_eq!(contract.propose(DEFAULT_TRACK, String::from("first"), None), Ok(0));
+            assert_eq!(contract.propose(DEFAULT_TRACK, String::from("second"), None), Ok(1));
+
+            let proposal = contract.get_proposal(0).unwrap();
+            assert_eq!(proposal.proposer, accounts().alice);
+            assert_eq!(proposal.start_time, 10);
+            assert_eq!(proposal.end_time, 10 + VOTING_PERIOD);
+            assert!(!proposal.executed);
+            // ProposalCreated + ProposalStateChanged por propuesta
+            assert_eq!(emitted_events(), 4);
+        }
+
+        #[ink::test]
+        fn propose_unknown_track_fails() {
+            let mut contract = contract();
+            assert_eq!(
+                contract.propose(7, String::from("nope"), None),
+                Err(Error::TrackNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn propose_invalid_track_fails() {
+            let mut contract = contract();
+            let mut track = treasury_track();
+            track.approval_threshold = 10_001;
+            assert_eq!(
+                contract.propose(
+                    DEFAULT_TRACK,
+                    String::from("bad track"),
+                    Some(ProposalAction::SetTrack { track_id: 1, track }),
+                ),
+                Err(Error::InvalidTrack)
+            );
+        }
+
+        #[ink::test]
+        fn propose_invalid_slash_fails() {
+            let mut contract = contract();
+            let action = ProposalAction::Slash {
+                account: accounts().bob,
+                basis_points: 0,
+                reason: String::from("none"),
+                destination: SlashDestination::Burn,
+            };
+            assert_eq!(
+                contract.propose(DEFAULT_TRACK, String::from("bad slash"), Some(action)),
+                Err(Error::InvalidSlash)
+            );
+        }
+
+        #[ink::test]
+        fn propose_empty_arkiv_address_fails() {
+            let mut contract = contract();
+            let action = ProposalAction::SetArkivAddress {
+                storage: AccountId::from([0x02; 32]),
+                arkiv_address: String::new(),
+            };
+            assert_eq!(
+                contract.propose(DEFAULT_TRACK, String::from("arkiv"), Some(action)),
+                Err(Error::InvalidAction)
+            );
+        }
+
+        #[ink::test]
+        fn reentrancy_lock_blocks_external_call_paths() {
+            let mut contract = contract();
+            contract.stake(100).unwrap();
+
+            contract.reentrancy_lock.set(&true);
+            assert_eq!(contract.stake(1), Err(Error::Reentrancy));
+            assert_eq!(contract.unstake(1), Err(Error::Reentrancy));
+            assert_eq!(contract.claim_rewards(), Err(Error::Reentrancy));
+            assert_eq!(contract.execute(0), Err(Error::Reentrancy));
+            assert_eq!(contract.fund_reward_token(accounts().django, 1), Err(Error::Reentrancy));
+            assert_eq!(contract.claim_token_rewards(accounts().django), Err(Error::Reentrancy));
+            assert_eq!(contract.claim_all_rewards(), Err(Error::Reentrancy));
+            assert_eq!(contract.vote(0, true), Err(Error::Reentrancy));
+            assert_eq!(
+                contract.vote_with_conviction(0, true, 1, Conviction::Locked1x),
+                Err(Error::Reentrancy)
+            );
+
+            contract.reentrancy_lock.set(&false);
+            assert_eq!(contract.stake(1), Ok(()));
+        }
+
+        #[ink::test]
+        fn reentrancy_lock_is_released_on_error() {
+            let mut contract = contract();
+            assert_eq!(contract.unstake(1), Err(Error::NoStake));
+            assert_eq!(contract.stake(1), Ok(()));
+        }
+
+        #[ink::test]
+        fn propose_requires_proposer_stake() {
+            let mut contract = contract();
+            pass_proposal(
+                &mut contract,
+                ProposalAction::SetTrack { track_id: 1, track: treasury_track() },
+            );
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                contract.propose(1, String::from("grant"), None),
+                Err(Error::InsufficientProposerStake)
+            );
+
+            contract.stake(100).unwrap();
+            assert!(contract.propose(1, String::from("grant"), None).is_ok());
+        }
+
+        #[ink::test]
+        fn vote_works() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            assert_eq!(contract.vote(0, true), Ok(()));
+            set_caller(accounts().bob);
+            assert_eq!(contract.vote(0, false), Ok(()));
+
+            let proposal = contract.get_proposal(0).unwrap();
+            assert_eq!(proposal.for_votes, 1000);
+            assert_eq!(proposal.against_votes, 1000);
+            assert_eq!(proposal.voters, vec![accounts().alice, accounts().bob]);
+        }
+
+        #[ink::test]
+        fn vote_uses_snapshot_voting_power() {
+            let mut contract = contract();
+            let token = AccountId::from(TOKEN);
+            mock_governance_token::set_votes(token, accounts().bob, 300);
+            test::advance_block::<DefaultEnvironment>();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+
+            // Lo delegado después del snapshot no cuenta
+            mock_governance_token::set_votes(token, accounts().bob, 5000);
+            mock_governance_token::set_votes(token, accounts().alice, 0);
+            test::advance_block::<DefaultEnvironment>();
+
+            assert_eq!(contract.vote(0, true), Ok(()));
+            set_caller(accounts().bob);
+            assert_eq!(contract.vote(0, false), Ok(()));
+            let proposal = contract.get_proposal(0).unwrap();
+            assert_eq!(proposal.for_votes, VOTES);
+            assert_eq!(proposal.against_votes, 300);
+        }
+
+        #[ink::test]
+        fn vote_without_voting_power_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            set_caller(AccountId::from([0x42; 32]));
+            assert_eq!(contract.vote(0, true), Err(Error::NoVotingPower));
+        }
+
+        #[ink::test]
+        fn vote_in_snapshot_block_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            // Sin bloque cerrado tras el snapshot el token no puede responder
+            let mut proposal = contract.get_proposal(0).unwrap();
+            proposal.snapshot_block = ink::env::block_number::<DefaultEnvironment>();
+            contract.proposals.insert(0, &proposal);
+            assert_eq!(contract.vote(0, true), Err(Error::CrossContractCallFailed));
+        }
+
+        #[ink::test]
+        fn vote_reads_the_configured_token() {
+            set_caller(accounts().alice);
+            mock_governance_token::seed_default_accounts(AccountId::from(TOKEN), VOTES);
+            let mut contract = DevKitShowcase::new(
+                AccountId::from([0x02; 32]),
+                VOTING_PERIOD,
+                QUORUM,
+                REWARD_RATE,
+                ClockMode::Timestamp,
+            );
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            assert_eq!(contract.vote(0, true), Err(Error::NoVotingPower));
+        }
+
+        #[ink::test]
+        fn vote_unknown_proposal_fails() {
+            let mut contract = contract();
+            assert_eq!(contract.vote(3, true), Err(Error::ProposalNotFound));
+        }
+
+        #[ink::test]
+        fn vote_twice_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote(0, true).unwrap();
+            assert_eq!(contract.vote(0, true), Err(Error::AlreadyVoted));
+        }
+
+        #[ink::test]
+        fn vote_after_period_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.vote(0, true), Err(Error::VotingPeriodEnded));
+        }
+
+        #[ink::test]
+        fn vote_with_conviction_applies_multiplier_and_lock() {
+            let mut contract = contract();
+            contract.stake(500).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+
+            assert_eq!(
+                contract.vote_with_conviction(0, true, 500, Conviction::Locked3x),
+                Ok(())
+            );
+
+            let proposal = contract.get_proposal(0).unwrap();
+            assert_eq!(proposal.for_votes, 1500);
+
+            let lock = contract.get_vote_lock(accounts().alice).unwrap();
+            assert_eq!(lock.amount, 500);
+            assert_eq!(lock.unlock_at, VOTING_PERIOD + VOTING_PERIOD * 4);
+        }
+
+        #[ink::test]
+        fn vote_with_no_conviction_counts_a_tenth() {
+            let mut contract = contract();
+            contract.stake(500).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote_with_conviction(0, false, 500, Conviction::None).unwrap();
+
+            assert_eq!(contract.get_proposal(0).unwrap().against_votes, 50);
+            assert_eq!(
+                contract.get_vote_lock(accounts().alice).unwrap().unlock_at,
+                VOTING_PERIOD
+            );
+        }
+
+        #[ink::test]
+        fn vote_with_conviction_checks_stake() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            assert_eq!(
+                contract.vote_with_conviction(0, true, 10, Conviction::Locked1x),
+                Err(Error::NoStake)
+            );
+
+            contract.stake(100).unwrap();
+            assert_eq!(
+                contract.vote_with_conviction(0, true, 0, Conviction::Locked1x),
+                Err(Error::InvalidAmount)
+            );
+            assert_eq!(
+                contract.vote_with_conviction(0, true, 101, Conviction::Locked1x),
+                Err(Error::InsufficientStake)
+            );
+            assert_eq!(
+                contract.vote_with_conviction(9, true, 100, Conviction::Locked1x),
+                Err(Error::ProposalNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn vote_with_conviction_is_capped_by_voting_power() {
+            let mut contract = contract();
+            // El stake es contable y no exige tokens de gobernanza
+            contract.stake(1_000_000).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+
+            assert_eq!(
+                contract.vote_with_conviction(0, true, VOTES + 1, Conviction::Locked6x),
+                Err(Error::InsufficientVotingPower)
+            );
+            assert!(contract.get_vote_lock(accounts().alice).is_none());
+
+            assert_eq!(
+                contract.vote_with_conviction(0, true, VOTES, Conviction::Locked6x),
+                Ok(())
+            );
+            assert_eq!(contract.get_proposal(0).unwrap().for_votes, VOTES * 6);
+        }
+
+        #[ink::test]
+        fn locked_stake_cannot_be_unstaked_until_unlocked() {
+            let mut contract = contract();
+            contract.stake(500).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote_with_conviction(0, true, 400, Conviction::Locked1x).unwrap();
+
+            assert_eq!(contract.unstake(101), Err(Error::StakeLocked));
+            assert_eq!(contract.unstake(100), Ok(()));
+
+            set_time(2 * VOTING_PERIOD - 1);
+            assert_eq!(contract.unlock(), Err(Error::LockNotExpired));
+
+            set_time(2 * VOTING_PERIOD);
+            assert_eq!(contract.unlock(), Ok(400));
+            assert!(contract.get_vote_lock(accounts().alice).is_none());
+            assert_eq!(contract.unstake(400), Ok(()));
+        }
+
+        #[ink::test]
+        fn unlock_without_lock_fails() {
+            let mut contract = contract();
+            assert_eq!(contract.unlock(), Err(Error::NoLock));
+        }
+
+        #[ink::test]
+        fn execute_works() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote(0, true).unwrap();
+
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Ok(()));
+            assert!(contract.get_proposal(0).unwrap().executed);
+            assert_eq!(contract.execute(0), Err(Error::AlreadyExecuted));
+        }
+
+        #[ink::test]
+        fn execute_unknown_proposal_fails() {
+            let mut contract = contract();
+            assert_eq!(contract.execute(0), Err(Error::ProposalNotFound));
+        }
+
+        #[ink::test]
+        fn execute_during_voting_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote(0, true).unwrap();
+            set_time(VOTING_PERIOD);
+            assert_eq!(contract.execute(0), Err(Error::VotingPeriodActive));
+        }
+
+        #[ink::test]
+        fn execute_without_quorum_fails() {
+            let mut contract = contract();
+            contract.stake(100).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote_with_conviction(0, true, 100, Conviction::Locked1x).unwrap();
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Err(Error::QuorumNotMet));
+        }
+
+        #[ink::test]
+        fn execute_rejected_proposal_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote(0, true).unwrap();
+            set_caller(accounts().bob);
+            contract.vote(0, false).unwrap();
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Err(Error::ProposalRejected));
+        }
+
+        #[ink::test]
+        fn execute_set_track_and_respect_timelock() {
+            let mut contract = contract();
+            pass_proposal(
+                &mut contract,
+                ProposalAction::SetTrack { track_id: 1, track: treasury_track() },
+            );
+            assert_eq!(contract.get_track(1), Some(treasury_track()));
+            assert_eq!(contract.get_tracks().len(), 2);
+
+            contract.stake(100).unwrap();
+            let start = VOTING_PERIOD + 1;
+            let proposal_id = contract.propose(1, String::from("grant"), None).unwrap();
+            contract.vote(proposal_id, true).unwrap();
+
+            set_time(start + 50 + 1);
+            assert_eq!(contract.execute(proposal_id), Err(Error::TimelockActive));
+
+            set_time(start + 50 + 20 + 1);
+            assert_eq!(contract.execute(proposal_id), Ok(()));
+        }
+
+        #[ink::test]
+        fn execute_slash_to_treasury() {
+            let mut contract = contract();
+            set_caller(accounts().bob);
+            contract.stake(1000).unwrap();
+            set_caller(accounts().charlie);
+            contract.stake(1000).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            contract.vote_with_conviction(0, true, 1000, Conviction::Locked1x).unwrap();
+
+            set_caller(accounts().alice);
+            pass_proposal(
+                &mut contract,
+                ProposalAction::Slash {
+                    account: accounts().charlie,
+                    basis_points: 2500,
+                    reason: String::from("misbehaviour"),
+                    destination: SlashDestination::Treasury,
+                },
+            );
+
+            let charlie = contract.get_staker_info(accounts().charlie).unwrap();
+            assert_eq!(charlie.amount, 750);
+            assert_eq!(contract.get_vote_lock(accounts().charlie).unwrap().amount, 750);
+            assert_eq!(contract.get_staking_pool(), 1750);
+            assert_eq!(contract.get_treasury_balance(), 250);
+        }
+
+        #[ink::test]
+        fn execute_slash_burn_keeps_other_rewards() {
+            let mut contract = contract();
+            set_caller(accounts().bob);
+            contract.stake(100).unwrap();
+            set_caller(accounts().charlie);
+            contract.stake(100).unwrap();
+
+            set_caller(accounts().alice);
+            pass_proposal(
+                &mut contract,
+                ProposalAction::Slash {
+                    account: accounts().charlie,
+                    basis_points: 10_000,
+                    reason: String::from("equivocation"),
+                    destination: SlashDestination::Burn,
+                },
+            );
+            assert_eq!(contract.get_treasury_balance(), 0);
+            assert_eq!(contract.get_staking_pool(), 100);
+
+            // Bob recibe la mitad hasta el slash y todo a partir de ahí
+            let slash_time = VOTING_PERIOD + 1;
+            set_caller(accounts().bob);
+            set_time(slash_time + 10);
+            let expected = REWARD_RATE * Balance::from(slash_time) / 2 + REWARD_RATE * 10;
+            assert_eq!(contract.claim_rewards(), Ok(expected));
+        }
+
+        fn set_guardians(contract: &mut DevKitShowcase, threshold: u32) {
+            let accounts = accounts();
+            pass_proposal(
+                contract,
+                ProposalAction::SetGuardians {
+                    guardians: vec![accounts.django, accounts.eve, accounts.frank],
+                    threshold,
+                },
+            );
+        }
+
+        #[ink::test]
+        fn set_guardians_validates_threshold() {
+            let mut contract = contract();
+            let action = ProposalAction::SetGuardians {
+                guardians: vec![accounts().django],
+                threshold: 2,
+            };
+            assert_eq!(
+                contract.propose(DEFAULT_TRACK, String::from("guardians"), Some(action)),
+                Err(Error::InvalidGuardians)
+            );
+
+            set_guardians(&mut contract, 2);
+            assert_eq!(contract.get_guardians().1, 2);
+            assert_eq!(contract.get_guardians().0.len(), 3);
+        }
+
+        #[ink::test]
+        fn optimistic_proposal_passes_without_votes() {
+            let mut contract = contract();
+            contract.propose_optimistic(DEFAULT_TRACK, String::from("ops"), None).unwrap();
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Ok(()));
+        }
+
+        #[ink::test]
+        fn optimistic_proposal_rejects_governance_actions() {
+            let mut contract = contract();
+            let governance_actions = [
+                ProposalAction::SetTrack { track_id: 1, track: treasury_track() },
+                ProposalAction::Slash {
+                    account: accounts().bob,
+                    basis_points: 1_000,
+                    reason: String::from("spam"),
+                    destination: SlashDestination::Burn,
+                },
+                ProposalAction::SetGuardians { guardians: vec![accounts().django], threshold: 1 },
+                ProposalAction::SetRewardRate { reward_rate: 1 },
+                ProposalAction::AcceptOracleOwnership { oracle: accounts().frank },
+                ProposalAction::AddOracleValidator { oracle: accounts().frank, validator: accounts().bob },
+                ProposalAction::AcceptArkivOwnership { storage: accounts().frank },
+            ];
+            for action in governance_actions {
+                assert_eq!(
+                    contract.propose_optimistic(DEFAULT_TRACK, String::from("ops"), Some(action)),
+                    Err(Error::ActionRequiresMajority)
+                );
+            }
+
+            let low_risk = ProposalAction::SetArkivAddress {
+                storage: accounts().frank,
+                arkiv_address: String::from("0xarkiv"),
+            };
+            assert_eq!(
+                contract.propose_optimistic(DEFAULT_TRACK, String::from("ops"), Some(low_risk)),
+                Ok(0)
+            );
+
+            // Track sin propuestas optimistas
+            pass_proposal(&mut contract, ProposalAction::SetTrack { track_id: 1, track: treasury_track() });
+            set_caller(accounts().alice);
+            contract.stake(100).unwrap();
+            assert_eq!(
+                contract.propose_optimistic(1, String::from("ops"), None),
+                Err(Error::OptimisticNotAllowed)
+            );
+        }
+
+        #[ink::test]
+        fn optimistic_proposal_blocked_by_objections() {
+            let mut contract = contract();
+            contract.propose_optimistic(DEFAULT_TRACK, String::from("ops"), None).unwrap();
+            contract.vote(0, false).unwrap();
+            set_caller(accounts().bob);
+            contract.vote(0, false).unwrap();
+
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Err(Error::ProposalRejected));
+        }
+
+        #[ink::test]
+        fn guardian_council_vetoes_proposal() {
+            let mut contract = contract();
+            set_guardians(&mut contract, 2);
+
+            let proposal_id = contract
+                .propose_optimistic(DEFAULT_TRACK, String::from("ops"), None)
+                .unwrap();
+
+            assert_eq!(contract.guardian_veto(proposal_id), Err(Error::NotGuardian));
+
+            set_caller(accounts().django);
+            assert_eq!(contract.guardian_veto(proposal_id), Ok(()));
+            assert_eq!(contract.guardian_veto(proposal_id), Err(Error::AlreadyVetoed));
+            assert!(!contract.get_proposal(proposal_id).unwrap().vetoed);
+
+            set_caller(accounts().eve);
+            assert_eq!(contract.guardian_veto(proposal_id), Ok(()));
+            assert!(contract.get_proposal(proposal_id).unwrap().vetoed);
+
+            set_caller(accounts().frank);
+            assert_eq!(contract.guardian_veto(proposal_id), Err(Error::ProposalVetoed));
+
+            set_time(2 * VOTING_PERIOD + 2);
+            assert_eq!(contract.execute(proposal_id), Err(Error::ProposalVetoed));
+        }
+
+        #[ink::test]
+        fn guardian_veto_after_execution_fails() {
+            let mut contract = contract();
+            set_guardians(&mut contract, 1);
+
+            // La propuesta 0 es la que instaló el consejo y ya se ejecutó
+            set_caller(accounts().django);
+            assert_eq!(contract.guardian_veto(0), Err(Error::AlreadyExecuted));
+            assert_eq!(contract.guardian_veto(9), Err(Error::ProposalNotFound));
+        }
+
+        fn fund_directly(contract: &mut DevKitShowcase, token: AccountId, reserve: Balance) {
+            // El depósito real hace una llamada PSP22, que no existe off-chain
+            let mut info = contract.reward_token_info.get(token).unwrap();
+            info.reserve = reserve;
+            contract.reward_token_info.insert(token, &info);
+        }
+
+        #[ink::test]
+        fn add_reward_token_checks() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            assert_eq!(contract.get_admin(), accounts().alice);
+
+            set_caller(accounts().bob);
+            assert_eq!(contract.add_reward_token(token, 1), Err(Error::NotAdmin));
+            assert_eq!(contract.set_reward_token_rate(token, 1), Err(Error::NotAdmin));
+
+            set_caller(accounts().alice);
+            assert_eq!(contract.add_reward_token(token, 1), Ok(()));
+            assert_eq!(contract.add_reward_token(token, 1), Err(Error::RewardTokenExists));
+
+            for i in 1..MAX_REWARD_TOKENS {
+                contract.add_reward_token(AccountId::from([0x10 + i as u8; 32]), 1).unwrap();
+            }
+            assert_eq!(
+                contract.add_reward_token(AccountId::from([0xff; 32]), 1),
+                Err(Error::TooManyRewardTokens)
+            );
+            assert_eq!(contract.get_reward_tokens().len(), MAX_REWARD_TOKENS);
+        }
+
+        #[ink::test]
+        fn token_rewards_are_capped_by_reserve() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            contract.add_reward_token(token, 10).unwrap();
+            fund_directly(&mut contract, token, 150);
+
+            contract.stake(100).unwrap();
+            set_caller(accounts().bob);
+            contract.stake(100).unwrap();
+
+            set_time(20);
+            contract.claim_rewards().unwrap();
+            set_caller(accounts().alice);
+            contract.claim_rewards().unwrap();
+
+            assert_eq!(contract.get_pending_rewards(accounts().alice), vec![(token, 75)]);
+            assert_eq!(contract.get_pending_rewards(accounts().bob), vec![(token, 75)]);
+            assert_eq!(contract.get_reward_tokens()[0].1.reserve, 0);
+        }
+
+        #[ink::test]
+        fn pending_rewards_omit_overflowing_tokens() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            let other = AccountId::from([0x11; 32]);
+            contract.add_reward_token(token, Balance::MAX).unwrap();
+            contract.add_reward_token(other, 1).unwrap();
+            fund_directly(&mut contract, token, Balance::MAX);
+            fund_directly(&mut contract, other, 1_000);
+            contract.stake(1).unwrap();
+
+            set_time(1);
+            assert_eq!(contract.get_pending_rewards(accounts().alice), vec![(other, 1)]);
+            assert_eq!(
+                contract.get_pending_token_rewards(accounts().alice, token),
+                Err(Error::ArithmeticOverflow)
+            );
+            assert_eq!(contract.get_pending_rewards(accounts().bob), vec![(token, 0), (other, 0)]);
+            assert_eq!(
+                contract.get_pending_token_rewards(accounts().alice, AccountId::from([0x12; 32])),
+                Err(Error::RewardTokenNotFound)
+            );
+        }
+
+        #[ink::test]
+        fn reward_token_rate_change_accrues_old_rate() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            contract.add_reward_token(token, 10).unwrap();
+            fund_directly(&mut contract, token, 1_000);
+            contract.stake(100).unwrap();
+
+            set_time(10);
+            assert_eq!(contract.set_reward_token_rate(token, 20), Ok(()));
+
+            set_time(20);
+            assert_eq!(contract.get_pending_rewards(accounts().alice), vec![(token, 300)]);
+        }
+
+        #[ink::test]
+        fn pending_rewards_are_simulated_without_writes() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            contract.add_reward_token(token, 2).unwrap();
+            fund_directly(&mut contract, token, 1_000);
+
+            contract.stake(100).unwrap();
+            set_caller(accounts().bob);
+            contract.stake(300).unwrap();
+
+            set_time(40);
+            assert_eq!(contract.get_pending_staking_rewards(accounts().alice), Ok(100));
+            assert_eq!(contract.get_pending_staking_rewards(accounts().bob), Ok(300));
+            assert_eq!(contract.get_pending_staking_rewards(accounts().charlie), Ok(0));
+            assert_eq!(contract.get_pending_rewards(accounts().alice), vec![(token, 20)]);
+
+            // La vista no liquida nada
+            assert_eq!(contract.get_staker_info(accounts().alice).unwrap().reward_debt, 0);
+            assert_eq!(contract.claim_rewards(), Ok(300));
+            set_caller(accounts().alice);
+            assert_eq!(contract.claim_rewards(), Ok(100));
+        }
+
+        #[ink::test]
+        fn apr_depends_on_rate_and_pool() {
+            let mut contract = contract();
+            assert_eq!(contract.get_apr(), Ok(0));
+
+            contract.stake(MILLIS_PER_YEAR * REWARD_RATE).unwrap();
+            // Una recompensa anual igual al pool es un 100%
+            assert_eq!(contract.get_apr(), Ok(10_000));
+        }
+
+        #[ink::test]
+        fn apr_in_block_number_mode() {
+            let mut by_blocks = DevKitShowcase::new(
+                AccountId::from([0x01; 32]),
+                VOTING_PERIOD,
+                QUORUM,
+                1,
+                ClockMode::BlockNumber,
+            );
+            by_blocks.stake(BLOCKS_PER_YEAR * 2).unwrap();
+            assert_eq!(by_blocks.get_apr(), Ok(5_000));
+        }
+
+        #[ink::test]
+        fn token_reward_claim_errors() {
+            let mut contract = contract();
+            let token = AccountId::from([0x10; 32]);
+            assert_eq!(contract.claim_token_rewards(token), Err(Error::RewardTokenNotFound));
+            assert_eq!(contract.fund_reward_token(token, 1), Err(Error::RewardTokenNotFound));
+
+            contract.add_reward_token(token, 10).unwrap();
+            assert_eq!(contract.fund_reward_token(token, 0), Err(Error::InvalidAmount));
+            assert_eq!(contract.claim_token_rewards(token), Err(Error::NoRewards));
+            assert_eq!(contract.claim_all_rewards(), Err(Error::NoRewards));
+        }
+
+        #[ink::test]
+        fn stake_works() {
+            let mut contract = contract();
+            assert_eq!(contract.stake(100), Ok(()));
+            assert_eq!(contract.stake(50), Ok(()));
+            assert_eq!(contract.get_staking_pool(), 150);
+            assert_eq!(contract.get_staker_info(accounts().alice).unwrap().amount, 150);
+            assert_eq!(emitted_events(), 2);
+        }
+
+        #[ink::test]
+        fn stake_zero_fails() {
+            let mut contract = contract();
+            assert_eq!(contract.stake(0), Err(Error::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn unstake_works() {
+            let mut contract = contract();
+            contract.stake(100).unwrap();
+            assert_eq!(contract.unstake(40), Ok(()));
+            assert_eq!(contract.get_staking_pool(), 60);
+            assert_eq!(contract.get_staker_info(accounts().alice).unwrap().amount, 60);
+        }
+
+        #[ink::test]
+        fn unstake_errors() {
+            let mut contract = contract();
+            assert_eq!(contract.unstake(1), Err(Error::NoStake));
+            contract.stake(100).unwrap();
+            assert_eq!(contract.unstake(101), Err(Error::InsufficientStake));
+        }
+
+        #[ink::test]
+        fn claim_rewards_splits_by_stake() {
+            let mut contract = contract();
+            contract.stake(100).unwrap();
+
+            set_caller(accounts().bob);
+            set_time(10);
+            contract.stake(300).unwrap();
+
+            set_time(20);
+            assert_eq!(contract.claim_rewards(), Ok(75));
+            set_caller(accounts().alice);
+            assert_eq!(contract.claim_rewards(), Ok(125));
+            assert_eq!(contract.get_staker_info(accounts().alice).unwrap().reward_debt, 0);
+        }
+
+        #[ink::test]
+        fn claim_rewards_errors() {
+            let mut contract = contract();
+            assert_eq!(contract.claim_rewards(), Err(Error::NoStake));
+            contract.stake(100).unwrap();
+            assert_eq!(contract.claim_rewards(), Err(Error::NoRewards));
+        }
+
+        #[ink::test]
+        fn propose_count_overflow_fails() {
+            let mut contract = contract();
+            contract.proposal_count = u32::MAX;
+            assert_eq!(
+                contract.propose(DEFAULT_TRACK, String::from("p"), None),
+                Err(Error::ArithmeticOverflow)
+            );
+        }
+
+        #[ink::test]
+        fn stake_at_balance_max_overflows() {
+            let mut contract = contract();
+            assert_eq!(contract.stake(Balance::MAX), Ok(()));
+            assert_eq!(contract.stake(1), Err(Error::ArithmeticOverflow));
+
+            set_caller(accounts().bob);
+            assert_eq!(contract.stake(1), Err(Error::ArithmeticOverflow));
+            assert_eq!(contract.get_staking_pool(), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn conviction_votes_at_balance_max() {
+            let mut contract = contract();
+            mock_governance_token::set_votes(AccountId::from(TOKEN), accounts().alice, Balance::MAX);
+            test::advance_block::<DefaultEnvironment>();
+            contract.stake(Balance::MAX).unwrap();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            assert_eq!(
+                contract.vote_with_conviction(0, true, Balance::MAX, Conviction::Locked6x),
+                Err(Error::ArithmeticOverflow)
+            );
+            assert_eq!(
+                contract.vote_with_conviction(0, true, Balance::MAX, Conviction::Locked1x),
+                Ok(())
+            );
+            assert_eq!(contract.get_proposal(0).unwrap().for_votes, Balance::MAX);
+
+            set_caller(accounts().bob);
+            assert_eq!(contract.vote(0, true), Err(Error::ArithmeticOverflow));
+        }
+
+        #[ink::test]
+        fn execute_tally_overflow_fails() {
+            let mut contract = contract();
+            contract.propose(DEFAULT_TRACK, String::from("p"), None).unwrap();
+            let mut proposal = contract.get_proposal(0).unwrap();
+            proposal.for_votes = Balance::MAX;
+            proposal.against_votes = 1;
+            contract.proposals.insert(0, &proposal);
+
+            set_time(VOTING_PERIOD + 1);
+            assert_eq!(contract.execute(0), Err(Error::ArithmeticOverflow));
+        }
+
+        #[ink::test]
+        fn reward_overflow_is_reported_not_zeroed() {
+            set_caller(accounts().alice);
+            set_time(0);
+            let mut contract = DevKitShowcase::new(
+                AccountId::from([0x01; 32]),
+                VOTING_PERIOD,
+                QUORUM,
+                Balance::MAX,
+                ClockMode::Timestamp,
+            );
+            contract.stake(1).unwrap();
+
+            set_time(1);
+            assert_eq!(contract.claim_rewards(), Err(Error::ArithmeticOverflow));
+
+            // El principal sigue pudiendo retirarse; lo no liquidado se pierde
+            contract.unstake(1).unwrap();
+            assert_eq!(contract.get_staking_pool(), 0);
+            assert_eq!(contract.get_staker_info(accounts().alice).unwrap().reward_debt, 0);
+
+            // Con el pool vacío el acumulador vuelve a avanzar
+            set_time(2);
+            contract.stake(1).unwrap();
+            assert_eq!(contract.get_staking_pool(), 1);
+        }
+
+        #[ink::test]
+        fn set_reward_rate_recovers_from_reward_overflow() {
+            set_caller(accounts().alice);
+            mock_governance_token::seed_default_accounts(AccountId::from(TOKEN), VOTES);
+            set_time(0);
+            let mut contract = DevKitShowcase::new(
+                AccountId::from(TOKEN),
+                VOTING_PERIOD,
+                QUORUM,
+                Balance::MAX,
+                ClockMode::Timestamp,
+            );
+            contract.stake(1).unwrap();
+
+            set_time(1);
+            assert_eq!(contract.claim_rewards(), Err(Error::ArithmeticOverflow));
+            assert_eq!(contract.stake(1), Err(Error::ArithmeticOverflow));
+
+            // Lo devengado con la tasa anterior no es representable y se pierde
+            pass_proposal(&mut contract, ProposalAction::SetRewardRate { reward_rate: REWARD_RATE });
+            let executed_at = ink::env::block_timestamp::<DefaultEnvironment>();
+            assert_eq!(contract.get_pending_staking_rewards(accounts().alice), Ok(0));
+
+            set_time(executed_at + 10);
+            assert_eq!(contract.claim_rewards(), Ok(REWARD_RATE * 10));
+            assert_eq!(contract.stake(1), Ok(()));
+        }
+    }
+
+    #[cfg(test)]
+    mod invariant_tests {
+        use super::*;
+        use ink::env::test;
+        use ink::env::DefaultEnvironment;
+        use proptest::prelude::*;
+
+        const STAKERS: usize = 6;
+
+        /// Operación aleatoria sobre el pool de staking
+        #[derive(Debug, Clone)]
+        enum Op {
+            Stake(usize, Balance),
+            Unstake(usize, Balance),
+            ClaimRewards(usize),
+            AdvanceTime(u64),
+        }
+
+        fn op_strategy() -> impl Strategy<Value = Op> {
+            prop_oneof![
+                (0..STAKERS, 1..1_000_000u128).prop_map(|(i, a)| Op::Stake(i, a)),
+                (0..STAKERS, 1..1_000_000u128).prop_map(|(i, a)| Op::Unstake(i, a)),
+                (0..STAKERS).prop_map(Op::ClaimRewards),
+                (0..10_000u64).prop_map(Op::AdvanceTime),
+            ]
+        }
+
+        fn stakers() -> [AccountId; STAKERS] {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            [
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+                accounts.eve,
+                accounts.frank,
+            ]
+        }
+
+        /// Ejecuta la secuencia comprobando los invariantes tras cada paso
+        fn run_ops(reward_rate: Balance, ops: Vec<Op>) {
+            test::run_test::<DefaultEnvironment, _>(|_| {
+                let stakers = stakers();
+                let mut now = 0u64;
+                test::set_block_timestamp::<DefaultEnvironment>(now);
+                let mut contract = DevKitShowcase::new(
+                    AccountId::from([0x01; 32]),
+                    100,
+                    1000,
+                    reward_rate,
+                    ClockMode::Timestamp,
+                );
+
+                // Recompensas emitidas mientras hubo stake y total reclamado
+                let mut funded: Balance = 0;
+                let mut claimed: Balance = 0;
+
+                for op in ops {
+                    match op {
+                        Op::Stake(i, amount) => {
+                            test::set_caller::<DefaultEnvironment>(stakers[i]);
+                            let _ = contract.stake(amount);
+                        }
+                        Op::Unstake(i, amount) => {
+                            test::set_caller::<DefaultEnvironment>(stakers[i]);
+                            let _ = contract.unstake(amount);
+                        }
+                        Op::ClaimRewards(i) => {
+                            test::set_caller::<DefaultEnvironment>(stakers[i]);
+                            if let Ok(rewards) = contract.claim_rewards() {
+                                claimed += rewards;
+                            }
+                        }
+                        Op::AdvanceTime(delta) => {
+                            if contract.get_staking_pool() > 0 {
+                                funded += reward_rate * Balance::from(delta);
+                            }
+                            now += delta;
+                            test::set_block_timestamp::<DefaultEnvironment>(now);
+                        }
+                    }
+
+                    let infos: Vec<StakerInfo> = stakers
+                        .iter()
+                        .filter_map(|staker| contract.get_staker_info(*staker))
+                        .collect();
+
+                    let staked: Balance = infos.iter().map(|info| info.amount).sum();
+                    assert_eq!(contract.get_staking_pool(), staked);
+
+                    let owed: Balance = infos.iter().map(|info| info.reward_debt).sum();
+                    assert!(
+                        claimed + owed <= funded,
+                        "distribuido {} > emitido {}",
+                        claimed + owed,
+                        funded
+                    );
+                }
+
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(256))]
+
+            #[test]
+            fn staking_accounting_invariants(
+                reward_rate in 0..1_000u128,
+                ops in prop::collection::vec(op_strategy(), 1..64),
+            ) {
+                run_ops(reward_rate, ops);
+            }
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use governance_token::{GovernanceTokenRef, Votes, PSP22};
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        /// Período de votación en ms, suficiente para votar antes de que cierre
+        const VOTING_PERIOD: u64 = 6_000;
+
+        fn wait_for_voting_period() {
+            std::thread::sleep(std::time::Duration::from_millis(VOTING_PERIOD + 1_000));
+        }
+
+        #[ink_e2e::test]
+        async fn propose_vote_execute_lifecycle<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // Alice delega el poder de voto de todo el suministro a Bob
+            let mut token_constructor = GovernanceTokenRef::new(1_000, None, None, 12);
+            let token = client
+                .instantiate("governance_token", &ink_e2e::alice(), &mut token_constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_builder = token.call_builder::<governance_token::GovernanceToken>();
+
+            let delegate = token_builder.delegate(ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            client
+                .call(&ink_e2e::alice(), &delegate)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let mut constructor = DevKitShowcaseRef::new(
+                token.account_id,
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::Timestamp,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            let propose = call_builder.propose(DEFAULT_TRACK, String::from("lifecycle"), None);
+            let proposal_id = client
+                .call(&ink_e2e::alice(), &propose)
+                .submit()
+                .await
+                .expect("propose failed")
+                .return_value()
+                .expect("propose returned an error");
+
+            let vote = call_builder.vote(proposal_id, true);
+            client
+                .call(&ink_e2e::bob(), &vote)
+                .submit()
+                .await
+                .expect("vote failed");
+
+            let execute = call_builder.execute(proposal_id);
+            let early = client.call(&ink_e2e::alice(), &execute).dry_run().await?;
+            assert_eq!(early.return_value(), Err(Error::VotingPeriodActive));
+
+            wait_for_voting_period();
+
+            // Un bloque nuevo para que avance el timestamp
+            let stake = call_builder.stake(1);
+            client
+                .call(&ink_e2e::charlie(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+
+            client
+                .call(&ink_e2e::alice(), &execute)
+                .submit()
+                .await
+                .expect("execute failed");
+
+            let get_proposal = call_builder.get_proposal(proposal_id);
+            let proposal = client
+                .call(&ink_e2e::alice(), &get_proposal)
+                .dry_run()
+                .await?
+                .return_value()
+                .expect("proposal not found");
+            assert!(proposal.executed);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn multi_staker_rewards_accrue<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut constructor = DevKitShowcaseRef::new(
+                AccountId::from([0x01; 32]),
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::Timestamp,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            let stake_alice = call_builder.stake(100);
+            client
+                .call(&ink_e2e::alice(), &stake_alice)
+                .submit()
+                .await
+                .expect("alice stake failed");
+
+            let stake_bob = call_builder.stake(300);
+            client
+                .call(&ink_e2e::bob(), &stake_bob)
+                .submit()
+                .await
+                .expect("bob stake failed");
+
+            let get_pool = call_builder.get_staking_pool();
+            let pool = client
+                .call(&ink_e2e::alice(), &get_pool)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(pool, 400);
+
+            wait_for_voting_period();
+
+            let claim = call_builder.claim_rewards();
+            let alice_rewards = client
+                .call(&ink_e2e::alice(), &claim)
+                .submit()
+                .await
+                .expect("alice claim failed")
+                .return_value()
+                .expect("alice had no rewards");
+            let bob_rewards = client
+                .call(&ink_e2e::bob(), &claim)
+                .submit()
+                .await
+                .expect("bob claim failed")
+                .return_value()
+                .expect("bob had no rewards");
+
+            assert!(alice_rewards > 0);
+            assert!(bob_rewards > alice_rewards);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn passed_proposal_adds_oracle_validator<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut oracle_constructor = HyperbridgeOracleRef::new(1);
+            let oracle = client
+                .instantiate("hyperbridge_oracle", &ink_e2e::alice(), &mut oracle_constructor)
+                .submit()
+                .await
+                .expect("oracle instantiate failed");
+            let mut oracle_builder = oracle.call_builder::<hyperbridge_oracle::HyperbridgeOracle>();
+
+            // Alice delega el poder de voto de todo el suministro a Bob
+            let mut token_constructor = GovernanceTokenRef::new(1_000, None, None, 12);
+            let token = client
+                .instantiate("governance_token", &ink_e2e::alice(), &mut token_constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_builder = token.call_builder::<governance_token::GovernanceToken>();
+
+            let delegate = token_builder.delegate(ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            client
+                .call(&ink_e2e::alice(), &delegate)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let mut constructor = DevKitShowcaseRef::new(
+                token.account_id,
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::Timestamp,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            // El owner inicial cede el oracle a la DAO, que acepta vía propuesta
+            let transfer_ownership = oracle_builder.transfer_ownership(contract.account_id);
+            client
+                .call(&ink_e2e::alice(), &transfer_ownership)
+                .submit()
+                .await
+                .expect("transfer_ownership failed");
+
+            let validator = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+            let actions = [
+                ProposalAction::AcceptOracleOwnership { oracle: oracle.account_id },
+                ProposalAction::AddOracleValidator { oracle: oracle.account_id, validator },
+            ];
+
+            let mut proposal_ids = Vec::new();
+            for action in actions {
+                let propose = call_builder.propose(DEFAULT_TRACK, String::from("oracle"), Some(action));
+                let proposal_id = client
+                    .call(&ink_e2e::alice(), &propose)
+                    .submit()
+                    .await
+                    .expect("propose failed")
+                    .return_value()
+                    .expect("propose returned an error");
+
+                let vote = call_builder.vote(proposal_id, true);
+                client
+                    .call(&ink_e2e::bob(), &vote)
+                    .submit()
+                    .await
+                    .expect("vote failed");
+
+                proposal_ids.push(proposal_id);
+            }
+
+            wait_for_voting_period();
+
+            // Un bloque nuevo para que avance el timestamp
+            let stake = call_builder.stake(1);
+            client
+                .call(&ink_e2e::charlie(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+
+            for proposal_id in proposal_ids {
+                let execute = call_builder.execute(proposal_id);
+                client
+                    .call(&ink_e2e::alice(), &execute)
+                    .submit()
+                    .await
+                    .expect("execute failed");
+            }
+
+            let get_owner = oracle_builder.get_owner();
+            let owner = client
+                .call(&ink_e2e::alice(), &get_owner)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(owner, contract.account_id);
+
+            let get_validators = oracle_builder.get_validators();
+            let validators = client
+                .call(&ink_e2e::alice(), &get_validators)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(validators, vec![validator]);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn passed_proposal_sets_arkiv_address<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut storage_constructor = ArkivStorageRef::new();
+            let storage = client
+                .instantiate("arkiv_storage", &ink_e2e::alice(), &mut storage_constructor)
+                .submit()
+                .await
+                .expect("storage instantiate failed");
+            let mut storage_builder = storage.call_builder::<arkiv_storage::ArkivStorage>();
+
+            // Alice delega el poder de voto de todo el suministro a Bob
+            let mut token_constructor = GovernanceTokenRef::new(1_000, None, None, 12);
+            let token = client
+                .instantiate("governance_token", &ink_e2e::alice(), &mut token_constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_builder = token.call_builder::<governance_token::GovernanceToken>();
+
+            let delegate = token_builder.delegate(ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            client
+                .call(&ink_e2e::alice(), &delegate)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let mut constructor = DevKitShowcaseRef::new(
+                token.account_id,
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::Timestamp,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            // El owner inicial cede el storage a la DAO, que acepta vía propuesta
+            let transfer_ownership = storage_builder.transfer_ownership(contract.account_id);
+            client
+                .call(&ink_e2e::alice(), &transfer_ownership)
+                .submit()
+                .await
+                .expect("transfer_ownership failed");
+
+            let actions = [
+                ProposalAction::AcceptArkivOwnership { storage: storage.account_id },
+                ProposalAction::SetArkivAddress {
+                    storage: storage.account_id,
+                    arkiv_address: String::from("0xarkiv"),
+                },
+            ];
+
+            let mut proposal_ids = Vec::new();
+            for action in actions {
+                let propose = call_builder.propose(DEFAULT_TRACK, String::from("arkiv"), Some(action));
+                let proposal_id = client
+                    .call(&ink_e2e::alice(), &propose)
+                    .submit()
+                    .await
+                    .expect("propose failed")
+                    .return_value()
+                    .expect("propose returned an error");
+
+                let vote = call_builder.vote(proposal_id, true);
+                client
+                    .call(&ink_e2e::bob(), &vote)
+                    .submit()
+                    .await
+                    .expect("vote failed");
+
+                proposal_ids.push(proposal_id);
+            }
+
+            wait_for_voting_period();
+
+            // Un bloque nuevo para que avance el timestamp
+            let stake = call_builder.stake(1);
+            client
+                .call(&ink_e2e::charlie(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+
+            for proposal_id in proposal_ids {
+                let execute = call_builder.execute(proposal_id);
+                client
+                    .call(&ink_e2e::alice(), &execute)
+                    .submit()
+                    .await
+                    .expect("execute failed");
+            }
+
+            let get_owner = storage_builder.get_owner();
+            let owner = client
+                .call(&ink_e2e::alice(), &get_owner)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(owner, contract.account_id);
+
+            // El owner anterior ya no administra el storage
+            let set_address = storage_builder.set_arkiv_address(String::from("0xother"));
+            let direct = client.call(&ink_e2e::alice(), &set_address).dry_run().await?;
+            assert!(direct.return_value().is_err());
+
+            let get_address = storage_builder.get_arkiv_address();
+            let address = client
+                .call(&ink_e2e::alice(), &get_address)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(address, Some(String::from("0xarkiv")));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn votes_use_governance_token_snapshot<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            // Alice delega el poder de voto de todo el suministro a Bob
+            let mut token_constructor = GovernanceTokenRef::new(1_000, None, None, 12);
+            let token = client
+                .instantiate("governance_token", &ink_e2e::alice(), &mut token_constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_builder = token.call_builder::<governance_token::GovernanceToken>();
+
+            let delegate = token_builder.delegate(ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            client
+                .call(&ink_e2e::alice(), &delegate)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let mut constructor = DevKitShowcaseRef::new(
+                token.account_id,
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::Timestamp,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            let propose = call_builder.propose(DEFAULT_TRACK, String::from("snapshot"), None);
+            let proposal_id = client
+                .call(&ink_e2e::alice(), &propose)
+                .submit()
+                .await
+                .expect("propose failed")
+                .return_value()
+                .expect("propose returned an error");
+
+            // Tras el snapshot Alice mueve 400 tokens a Charlie, que se delega
+            let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+            let transfer = token_builder.transfer(charlie, 400, Vec::new());
+            client
+                .call(&ink_e2e::alice(), &transfer)
+                .submit()
+                .await
+                .expect("transfer failed");
+
+            let delegate_self = token_builder.delegate(charlie);
+            client
+                .call(&ink_e2e::charlie(), &delegate_self)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let vote = call_builder.vote(proposal_id, true);
+            let late = client.call(&ink_e2e::charlie(), &vote).dry_run().await?;
+            assert_eq!(late.return_value(), Err(Error::NoVotingPower));
+
+            // El stake no da peso propio a los votos con convicción
+            let stake = call_builder.stake(1_000_000);
+            client
+                .call(&ink_e2e::charlie(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+            let conviction_vote =
+                call_builder.vote_with_conviction(proposal_id, true, 1_000_000, Conviction::Locked6x);
+            let staked = client.call(&ink_e2e::charlie(), &conviction_vote).dry_run().await?;
+            assert_eq!(staked.return_value(), Err(Error::InsufficientVotingPower));
+
+            client
+                .call(&ink_e2e::bob(), &vote)
+                .submit()
+                .await
+                .expect("vote failed");
+
+            let get_proposal = call_builder.get_proposal(proposal_id);
+            let proposal = client
+                .call(&ink_e2e::alice(), &get_proposal)
+                .dry_run()
+                .await?
+                .return_value()
+                .expect("proposal not found");
+            assert_eq!(proposal.for_votes, 1_000);
+
+            let get_votes = token_builder.get_votes(ink_e2e::account_id(ink_e2e::AccountKeyring::Bob));
+            let current = client
+                .call(&ink_e2e::alice(), &get_votes)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(current, 600);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn malicious_token_cannot_reenter_claims<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut token_constructor = malicious_token::MaliciousTokenRef::new();
+            let token = client
+                .instantiate("malicious_token", &ink_e2e::alice(), &mut token_constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_builder = token.call_builder::<malicious_token::MaliciousToken>();
+
+            let mut constructor = DevKitShowcaseRef::new(
+                AccountId::from([0x01; 32]),
+                VOTING_PERIOD,
+                1000,
+                1,
+                ClockMode::BlockNumber,
+            );
+            let contract = client
+                .instantiate("devkit_showcase", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<DevKitShowcase>();
+
+            let set_target = token_builder.set_target(contract.account_id);
+            client
+                .call(&ink_e2e::alice(), &set_target)
+                .submit()
+                .await
+                .expect("set_target failed");
+
+            let add_token = call_builder.add_reward_token(token.account_id, 1_000);
+            client
+                .call(&ink_e2e::alice(), &add_token)
+                .submit()
+                .await
+                .expect("add_reward_token failed");
+
+            let fund = call_builder.fund_reward_token(token.account_id, 1_000_000);
+            client
+                .call(&ink_e2e::alice(), &fund)
+                .submit()
+                .await
+                .expect("fund_reward_token failed");
+
+            let stake = call_builder.stake(100);
+            client
+                .call(&ink_e2e::bob(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+
+            // Un bloque más para que se devenguen recompensas
+            client
+                .call(&ink_e2e::charlie(), &stake)
+                .submit()
+                .await
+                .expect("stake failed");
+
+            let claim = call_builder.claim_token_rewards(token.account_id);
+            let claimed = client
+                .call(&ink_e2e::bob(), &claim)
+                .submit()
+                .await
+                .expect("claim failed")
+                .return_value()
+                .expect("claim returned an error");
+            assert!(claimed > 0);
+
+            let get_attempts = token_builder.get_reentry_attempts();
+            let attempts = client
+                .call(&ink_e2e::alice(), &get_attempts)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(attempts, 1);
+
+            // La reentrada está denegada: ni el claim ni el propose llegan a ejecutarse
+            let get_result = token_builder.get_reentry_result();
+            let result = client
+                .call(&ink_e2e::alice(), &get_result)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(result, None);
+
+            let get_propose_result = token_builder.get_propose_result();
+            let propose_result = client
+                .call(&ink_e2e::alice(), &get_propose_result)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(propose_result, None);
+
+            let get_proposal = call_builder.get_proposal(0);
+            let proposal = client
+                .call(&ink_e2e::alice(), &get_proposal)
+                .dry_run()
+                .await?
+                .return_value();
+            assert!(proposal.is_none());
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,156 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Malicious Token - contrato de prueba para los e2e de DevKit Showcase
+///
+/// Implementa lo mínimo de PSP22 para registrarse como token de recompensa.
+/// En cada `transfer` intenta volver a entrar en `claim_token_rewards` y en
+/// `propose` del contrato objetivo y guarda los resultados para que el test
+/// los compruebe.
+/// No mueve balances: solo sirve para probar la protección de reentrada.
+pub use self::malicious_token::{MaliciousToken, MaliciousTokenRef};
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Errores estándar de PSP22
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// Subconjunto de PSP22 que usa DevKit Showcase
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Transferir tokens (aquí: intentar reentrada)
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    /// Transferir tokens con allowance (aquí: no hace nada)
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+}
+
+#[ink::contract]
+mod malicious_token {
+    use super::PSP22Error;
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    /// Resultado de la reentrada; el error del objetivo se guarda como índice SCALE
+    pub type ReentryResult = Result<Balance, u8>;
+
+    /// Resultado del `propose` reentrante (id de la propuesta o índice del error)
+    pub type ProposeResult = Result<u32, u8>;
+
+    /// Storage del contrato
+    #[ink(storage)]
+    pub struct MaliciousToken {
+        /// Contrato al que se intenta volver a entrar
+        target: Option<AccountId>,
+        /// Resultado del último intento (`None` si la llamada no llegó a ejecutarse)
+        reentry_result: Option<ReentryResult>,
+        /// Resultado del último `propose` reentrante
+        propose_result: Option<ProposeResult>,
+        /// Número de intentos de reentrada
+        reentry_attempts: u32,
+    }
+
+    impl MaliciousToken {
+        /// Constructor del contrato
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                target: None,
+                reentry_result: None,
+                propose_result: None,
+                reentry_attempts: 0,
+            }
+        }
+
+        /// Fijar el contrato objetivo
+        #[ink(message)]
+        pub fn set_target(&mut self, target: AccountId) {
+            self.target = Some(target);
+        }
+
+        /// Obtener el resultado del último intento de reentrada
+        #[ink(message)]
+        pub fn get_reentry_result(&self) -> Option<ReentryResult> {
+            self.reentry_result
+        }
+
+        /// Obtener el resultado del último `propose` reentrante
+        #[ink(message)]
+        pub fn get_propose_result(&self) -> Option<ProposeResult> {
+            self.propose_result
+        }
+
+        /// Obtener el número de intentos de reentrada
+        #[ink(message)]
+        pub fn get_reentry_attempts(&self) -> u32 {
+            self.reentry_attempts
+        }
+    }
+
+    impl super::PSP22 for MaliciousToken {
+        #[ink(message)]
+        fn transfer(&mut self, _to: AccountId, _value: Balance, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            let Some(target) = self.target else {
+                return Ok(());
+            };
+
+            self.reentry_attempts += 1;
+            self.reentry_result = build_call::<Environment>()
+                .call(target)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("claim_token_rewards")))
+                        .push_arg(self.env().account_id()),
+                )
+                .returns::<ReentryResult>()
+                .try_invoke()
+                .ok()
+                .and_then(|result| result.ok());
+
+            // `None` se codifica igual para cualquier `Option<ProposalAction>`
+            self.propose_result = build_call::<Environment>()
+                .call(target)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("propose")))
+                        .push_arg(0u16)
+                        .push_arg(String::from("reentry"))
+                        .push_arg(Option::<()>::None),
+                )
+                .returns::<ProposeResult>()
+                .try_invoke()
+                .ok()
+                .and_then(|result| result.ok());
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            _from: AccountId,
+            _to: AccountId,
+            _value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            Ok(())
+        }
+    }
+}
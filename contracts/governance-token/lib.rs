@@ -0,0 +1,761 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Governance Token - PSP22 con extensión de votos
+///
+/// Token de gobernanza para DevKit Showcase: PSP22 estándar más
+/// delegación de votos y checkpoints por bloque para consultar el
+/// poder de voto histórico.
+///
+/// Generado con Polkadot DevKit para sub0 Hackathon 2025
+pub use self::governance_token::{GovernanceToken, GovernanceTokenRef};
+
+use ink::prelude::string::String;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Balance del entorno por defecto
+pub type Balance = u128;
+
+/// Número de bloque del entorno por defecto
+pub type BlockNumber = u32;
+
+/// Errores estándar de PSP22
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// Errores de la extensión de votos
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum VotesError {
+    /// Se consultó un bloque que aún no ha terminado
+    FutureLookup,
+}
+
+/// Interfaz PSP22
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Suministro total
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+
+    /// Balance de una cuenta
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> Balance;
+
+    /// Cantidad que `spender` puede gastar en nombre de `owner`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+
+    /// Transferir tokens del caller a `to`
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: Balance, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    /// Transferir tokens de `from` a `to` usando el allowance del caller
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Fijar el allowance de `spender`
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+
+    /// Aumentar el allowance de `spender`
+    #[ink(message)]
+    fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error>;
+
+    /// Reducir el allowance de `spender`
+    #[ink(message)]
+    fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error>;
+}
+
+/// Metadata PSP22
+#[ink::trait_definition]
+pub trait PSP22Metadata {
+    /// Nombre del token
+    #[ink(message)]
+    fn token_name(&self) -> Option<String>;
+
+    /// Símbolo del token
+    #[ink(message)]
+    fn token_symbol(&self) -> Option<String>;
+
+    /// Decimales del token
+    #[ink(message)]
+    fn token_decimals(&self) -> u8;
+}
+
+/// Extensión de votos: delegación y poder de voto histórico
+#[ink::trait_definition]
+pub trait Votes {
+    /// Poder de voto actual de una cuenta
+    #[ink(message)]
+    fn get_votes(&self, account: AccountId) -> Balance;
+
+    /// Poder de voto de una cuenta al final de `block`
+    #[ink(message)]
+    fn get_past_votes(&self, account: AccountId, block: BlockNumber) -> Result<Balance, VotesError>;
+
+    /// Cuenta a la que delega `account`
+    #[ink(message)]
+    fn delegates(&self, account: AccountId) -> Option<AccountId>;
+
+    /// Delegar el poder de voto del caller (puede ser a sí mismo)
+    #[ink(message)]
+    fn delegate(&mut self, delegatee: AccountId) -> Result<(), PSP22Error>;
+}
+
+#[ink::contract]
+mod governance_token {
+    use super::{PSP22Error, VotesError};
+    use ink::storage::Mapping;
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+
+    /// Storage del contrato
+    #[ink(storage)]
+    pub struct GovernanceToken {
+        /// Suministro total
+        total_supply: Balance,
+        /// Balances
+        balances: Mapping<AccountId, Balance>,
+        /// Allowances (owner, spender)
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Nombre
+        name: Option<String>,
+        /// Símbolo
+        symbol: Option<String>,
+        /// Decimales
+        decimals: u8,
+        /// Delegado de cada cuenta
+        delegates: Mapping<AccountId, AccountId>,
+        /// Checkpoints de votos (cuenta, índice)
+        checkpoints: Mapping<(AccountId, u32), Checkpoint>,
+        /// Número de checkpoints por cuenta
+        num_checkpoints: Mapping<AccountId, u32>,
+    }
+
+    /// Poder de voto de una cuenta a partir de un bloque
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Checkpoint {
+        /// Bloque desde el que aplica
+        pub block: BlockNumber,
+        /// Votos
+        pub votes: Balance,
+    }
+
+    /// Eventos del contrato
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        pub from: Option<AccountId>,
+        #[ink(topic)]
+        pub to: Option<AccountId>,
+        pub value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        pub owner: AccountId,
+        #[ink(topic)]
+        pub spender: AccountId,
+        pub amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        pub delegator: AccountId,
+        pub from_delegate: Option<AccountId>,
+        pub to_delegate: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct DelegateVotesChanged {
+        #[ink(topic)]
+        pub delegate: AccountId,
+        pub previous_votes: Balance,
+        pub new_votes: Balance,
+    }
+
+    impl GovernanceToken {
+        /// Constructor del contrato
+        ///
+        /// Acuña `total_supply` al caller. Como en ERC20Votes, los balances
+        /// solo cuentan como votos una vez delegados (también a uno mismo).
+        #[ink(constructor)]
+        pub fn new(
+            total_supply: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            let caller = Self::env().caller();
+            let mut balances = Mapping::default();
+            balances.insert(caller, &total_supply);
+
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: total_supply,
+            });
+
+            Self {
+                total_supply,
+                balances,
+                allowances: Mapping::default(),
+                name,
+                symbol,
+                decimals,
+                delegates: Mapping::default(),
+                checkpoints: Mapping::default(),
+                num_checkpoints: Mapping::default(),
+            }
+        }
+
+        /// Obtener el número de checkpoints de una cuenta
+        #[ink(message)]
+        pub fn num_checkpoints(&self, account: AccountId) -> u32 {
+            self.num_checkpoints.get(account).unwrap_or(0)
+        }
+
+        /// Obtener un checkpoint de una cuenta
+        #[ink(message)]
+        pub fn checkpoint(&self, account: AccountId, index: u32) -> Option<Checkpoint> {
+            self.checkpoints.get((account, index))
+        }
+
+        /// Mover balance entre cuentas y el poder de voto entre sus delegados
+        fn transfer_from_to(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+
+            if from == to || value == 0 {
+                return Ok(());
+            }
+
+            let to_balance = self.balance_of_impl(to);
+            let new_to_balance = to_balance
+                .checked_add(value)
+                .ok_or(PSP22Error::Custom(String::from("Overflow")))?;
+
+            self.balances.insert(from, &(from_balance - value));
+            self.balances.insert(to, &new_to_balance);
+
+            self.move_voting_power(self.delegates.get(from), self.delegates.get(to), value)?;
+
+            Self::env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Fijar un allowance y emitir `Approval`
+        fn approve_impl(&mut self, owner: AccountId, spender: AccountId, value: Balance) {
+            self.allowances.insert((owner, spender), &value);
+
+            Self::env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+        }
+
+        fn balance_of_impl(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        fn allowance_impl(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        /// Votos actuales de una cuenta según su último checkpoint
+        fn latest_votes(&self, account: AccountId) -> Balance {
+            let count = self.num_checkpoints(account);
+            if count == 0 {
+                return 0;
+            }
+            self.checkpoints
+                .get((account, count - 1))
+                .map(|checkpoint| checkpoint.votes)
+                .unwrap_or(0)
+        }
+
+        /// Transferir poder de voto de un delegado a otro
+        fn move_voting_power(
+            &mut self,
+            src: Option<AccountId>,
+            dst: Option<AccountId>,
+            amount: Balance,
+        ) -> Result<(), PSP22Error> {
+            if src == dst || amount == 0 {
+                return Ok(());
+            }
+
+            if let Some(src) = src {
+                let previous = self.latest_votes(src);
+                let new_votes = previous
+                    .checked_sub(amount)
+                    .ok_or(PSP22Error::Custom(String::from("Underflow")))?;
+                self.write_checkpoint(src, previous, new_votes);
+            }
+
+            if let Some(dst) = dst {
+                let previous = self.latest_votes(dst);
+                let new_votes = previous
+                    .checked_add(amount)
+                    .ok_or(PSP22Error::Custom(String::from("Overflow")))?;
+                self.write_checkpoint(dst, previous, new_votes);
+            }
+
+            Ok(())
+        }
+
+        /// Registrar el nuevo poder de voto de `delegate` en el bloque actual
+        ///
+        /// Varios cambios en el mismo bloque reescriben el mismo checkpoint.
+        fn write_checkpoint(&mut self, delegate: AccountId, previous_votes: Balance, new_votes: Balance) {
+            let block = Self::env().block_number();
+            let count = self.num_checkpoints(delegate);
+
+            let last = if count > 0 {
+                self.checkpoints.get((delegate, count - 1))
+            } else {
+                None
+            };
+
+            match last {
+                Some(checkpoint) if checkpoint.block == block => {
+                    self.checkpoints.insert((delegate, count - 1), &Checkpoint {
+                        block,
+                        votes: new_votes,
+                    });
+                }
+                _ => {
+                    self.checkpoints.insert((delegate, count), &Checkpoint {
+                        block,
+                        votes: new_votes,
+                    });
+                    self.num_checkpoints.insert(delegate, &(count + 1));
+                }
+            }
+
+            Self::env().emit_event(DelegateVotesChanged {
+                delegate,
+                previous_votes,
+                new_votes,
+            });
+        }
+    }
+
+    impl super::PSP22 for GovernanceToken {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balance_of_impl(owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowance_impl(owner, spender)
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: Balance, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            let from = self.env().caller();
+            self.transfer_from_to(from, to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let caller = self.env().caller();
+            if caller == from {
+                return self.transfer_from_to(from, to, value);
+            }
+
+            let allowance = self.allowance_impl(from, caller);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+
+            self.transfer_from_to(from, to, value)?;
+            self.approve_impl(from, caller, allowance - value);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            self.approve_impl(owner, spender, value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(owner, spender).saturating_add(delta_value);
+            self.approve_impl(owner, spender, allowance);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(&mut self, spender: AccountId, delta_value: Balance) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance_impl(owner, spender)
+                .checked_sub(delta_value)
+                .ok_or(PSP22Error::InsufficientAllowance)?;
+            self.approve_impl(owner, spender, allowance);
+            Ok(())
+        }
+    }
+
+    impl super::PSP22Metadata for GovernanceToken {
+        #[ink(message)]
+        fn token_name(&self) -> Option<String> {
+            self.name.clone()
+        }
+
+        #[ink(message)]
+        fn token_symbol(&self) -> Option<String> {
+            self.symbol.clone()
+        }
+
+        #[ink(message)]
+        fn token_decimals(&self) -> u8 {
+            self.decimals
+        }
+    }
+
+    impl super::Votes for GovernanceToken {
+        #[ink(message)]
+        fn get_votes(&self, account: AccountId) -> Balance {
+            self.latest_votes(account)
+        }
+
+        /// Búsqueda binaria del último checkpoint con `block <= block`
+        #[ink(message)]
+        fn get_past_votes(&self, account: AccountId, block: BlockNumber) -> Result<Balance, VotesError> {
+            if block >= self.env().block_number() {
+                return Err(VotesError::FutureLookup);
+            }
+
+            let mut low = 0;
+            let mut high = self.num_checkpoints(account);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let checkpoint = self.checkpoints.get((account, mid)).unwrap_or(Checkpoint {
+                    block: 0,
+                    votes: 0,
+                });
+                if checkpoint.block > block {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            if high == 0 {
+                return Ok(0);
+            }
+
+            Ok(self.checkpoints
+                .get((account, high - 1))
+                .map(|checkpoint| checkpoint.votes)
+                .unwrap_or(0))
+        }
+
+        #[ink(message)]
+        fn delegates(&self, account: AccountId) -> Option<AccountId> {
+            self.delegates.get(account)
+        }
+
+        #[ink(message)]
+        fn delegate(&mut self, delegatee: AccountId) -> Result<(), PSP22Error> {
+            let delegator = self.env().caller();
+            let previous = self.delegates.get(delegator);
+
+            self.delegates.insert(delegator, &delegatee);
+
+            Self::env().emit_event(DelegateChanged {
+                delegator,
+                from_delegate: previous,
+                to_delegate: delegatee,
+            });
+
+            let balance = self.balance_of_impl(delegator);
+            self.move_voting_power(previous, Some(delegatee), balance)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{PSP22, PSP22Metadata, Votes};
+        use ink::env::test;
+        use ink::env::DefaultEnvironment;
+
+        const SUPPLY: Balance = 1_000;
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        fn advance_block() {
+            test::advance_block::<DefaultEnvironment>();
+        }
+
+        fn token() -> GovernanceToken {
+            set_caller(accounts().alice);
+            GovernanceToken::new(
+                SUPPLY,
+                Some(String::from("DevKit Governance")),
+                Some(String::from("DKG")),
+                12,
+            )
+        }
+
+        #[ink::test]
+        fn new_mints_to_caller() {
+            let token = token();
+            assert_eq!(token.total_supply(), SUPPLY);
+            assert_eq!(token.balance_of(accounts().alice), SUPPLY);
+            assert_eq!(token.token_name(), Some(String::from("DevKit Governance")));
+            assert_eq!(token.token_symbol(), Some(String::from("DKG")));
+            assert_eq!(token.token_decimals(), 12);
+            assert_eq!(test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let mut token = token();
+            assert_eq!(token.transfer(accounts().bob, 300, Vec::new()), Ok(()));
+            assert_eq!(token.balance_of(accounts().alice), 700);
+            assert_eq!(token.balance_of(accounts().bob), 300);
+            assert_eq!(
+                token.transfer(accounts().bob, 701, Vec::new()),
+                Err(PSP22Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_uses_allowance() {
+            let mut token = token();
+            token.approve(accounts().bob, 100).unwrap();
+            token.increase_allowance(accounts().bob, 50).unwrap();
+            token.decrease_allowance(accounts().bob, 20).unwrap();
+            assert_eq!(token.allowance(accounts().alice, accounts().bob), 130);
+            assert_eq!(
+                token.decrease_allowance(accounts().bob, 131),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                token.transfer_from(accounts().alice, accounts().charlie, 131, Vec::new()),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(
+                token.transfer_from(accounts().alice, accounts().charlie, 100, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(token.balance_of(accounts().charlie), 100);
+            assert_eq!(token.allowance(accounts().alice, accounts().bob), 30);
+        }
+
+        #[ink::test]
+        fn balances_count_as_votes_only_when_delegated() {
+            let mut token = token();
+            assert_eq!(token.get_votes(accounts().alice), 0);
+
+            token.delegate(accounts().alice).unwrap();
+            assert_eq!(token.delegates(accounts().alice), Some(accounts().alice));
+            assert_eq!(token.get_votes(accounts().alice), SUPPLY);
+
+            token.delegate(accounts().bob).unwrap();
+            assert_eq!(token.get_votes(accounts().alice), 0);
+            assert_eq!(token.get_votes(accounts().bob), SUPPLY);
+        }
+
+        #[ink::test]
+        fn transfers_move_delegated_votes() {
+            let mut token = token();
+            token.delegate(accounts().alice).unwrap();
+
+            set_caller(accounts().bob);
+            token.delegate(accounts().charlie).unwrap();
+
+            set_caller(accounts().alice);
+            token.transfer(accounts().bob, 400, Vec::new()).unwrap();
+            assert_eq!(token.get_votes(accounts().alice), 600);
+            assert_eq!(token.get_votes(accounts().charlie), 400);
+            assert_eq!(token.get_votes(accounts().bob), 0);
+        }
+
+        #[ink::test]
+        fn same_block_changes_share_a_checkpoint() {
+            let mut token = token();
+            token.delegate(accounts().alice).unwrap();
+            token.transfer(accounts().bob, 100, Vec::new()).unwrap();
+            assert_eq!(token.num_checkpoints(accounts().alice), 1);
+
+            advance_block();
+            token.transfer(accounts().bob, 100, Vec::new()).unwrap();
+            assert_eq!(token.num_checkpoints(accounts().alice), 2);
+            assert_eq!(token.checkpoint(accounts().alice, 1).unwrap().votes, 800);
+        }
+
+        #[ink::test]
+        fn get_past_votes_reads_history() {
+            let mut token = token();
+            let start = ink::env::block_number::<DefaultEnvironment>();
+            token.delegate(accounts().alice).unwrap();
+
+            advance_block();
+            token.transfer(accounts().bob, 250, Vec::new()).unwrap();
+
+            advance_block();
+            advance_block();
+            token.transfer(accounts().bob, 250, Vec::new()).unwrap();
+
+            advance_block();
+            assert_eq!(token.get_past_votes(accounts().alice, start), Ok(1_000));
+            assert_eq!(token.get_past_votes(accounts().alice, start + 1), Ok(750));
+            assert_eq!(token.get_past_votes(accounts().alice, start + 2), Ok(750));
+            assert_eq!(token.get_past_votes(accounts().alice, start + 3), Ok(500));
+            assert_eq!(token.get_past_votes(accounts().bob, start + 3), Ok(0));
+            assert_eq!(
+                token.get_past_votes(accounts().alice, start + 4),
+                Err(VotesError::FutureLookup)
+            );
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use crate::{PSP22, Votes};
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn transfer_and_delegate<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor =
+                GovernanceTokenRef::new(1_000, None, Some(String::from("DKG")), 12);
+            let contract = client
+                .instantiate("governance_token", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("instantiate failed");
+            let mut call_builder = contract.call_builder::<GovernanceToken>();
+
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+
+            let delegate = call_builder.delegate(bob);
+            client
+                .call(&ink_e2e::alice(), &delegate)
+                .submit()
+                .await
+                .expect("delegate failed");
+
+            let transfer = call_builder.transfer(bob, 400, Vec::new());
+            client
+                .call(&ink_e2e::alice(), &transfer)
+                .submit()
+                .await
+                .expect("transfer failed");
+
+            let balance_of = call_builder.balance_of(bob);
+            let balance = client
+                .call(&ink_e2e::alice(), &balance_of)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(balance, 400);
+
+            // Los votos de alice (delegados a bob) bajan con la transferencia
+            let get_votes = call_builder.get_votes(bob);
+            let votes = client
+                .call(&ink_e2e::alice(), &get_votes)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(votes, 600);
+
+            // El checkpoint de la transferencia fija el bloque a consultar
+            let checkpoint = call_builder.checkpoint(bob, 1);
+            let transfer_block = client
+                .call(&ink_e2e::alice(), &checkpoint)
+                .dry_run()
+                .await?
+                .return_value()
+                .expect("checkpoint missing")
+                .block;
+
+            // Otra transacción para cerrar el bloque de la transferencia
+            let approve = call_builder.approve(bob, 1);
+            client
+                .call(&ink_e2e::alice(), &approve)
+                .submit()
+                .await
+                .expect("approve failed");
+
+            let past_votes = call_builder.get_past_votes(bob, transfer_block);
+            let votes = client
+                .call(&ink_e2e::alice(), &past_votes)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(votes, Ok(600));
+
+            let past_votes = call_builder.get_past_votes(bob, transfer_block - 1);
+            let votes = client
+                .call(&ink_e2e::alice(), &past_votes)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(votes, Ok(1_000));
+
+            Ok(())
+        }
+    }
+}
@@ -6,43 +6,457 @@
 /// demostrando interoperabilidad entre Polkadot y otras blockchains.
 /// 
 /// Generado con Polkadot DevKit para sub0 Hackathon 2025
+pub use self::hyperbridge_oracle::{
+    ChainId, Error, HyperbridgeOracle, HyperbridgeOracleRef, PriceData, StateProof, StateRoot,
+    StorageSlot,
+};
+
+use ink::prelude::string::String;
+
+/// Interfaz de consulta para contratos consumidores
+///
+/// Otros contratos la usan vía `ink::contract_ref!(PriceOracle)` con la
+/// feature `ink-as-dependency`.
+#[ink::trait_definition]
+pub trait PriceOracle {
+    /// Último precio finalizado, si está dentro de su heartbeat
+    #[ink(message)]
+    fn latest_price(&self, symbol: String) -> Result<PriceData, Error>;
+
+    /// Precio de un round finalizado
+    #[ink(message)]
+    fn price_at_round(&self, symbol: String, round_id: u32) -> Result<PriceData, Error>;
+
+    /// Decimales del feed de un símbolo
+    #[ink(message)]
+    fn decimals(&self, symbol: String) -> Result<u8, Error>;
+}
+
+/// Verificación de proofs Merkle-Patricia (trie seguro estilo Ethereum/ISMP)
+///
+/// Los nodos están codificados en RLP y referenciados por keccak256; el path
+/// de una clave es keccak256(clave).
+pub mod trie {
+    use crate::Error;
+    use ink::env::hash::{HashOutput, Keccak256};
+    use ink::prelude::vec::Vec;
+
+    /// Elemento RLP: payload y bytes completos (cabecera incluida)
+    struct Item<'a> {
+        is_list: bool,
+        payload: &'a [u8],
+        raw: &'a [u8],
+    }
+
+    /// Referencia a un nodo hijo
+    enum NodeRef<'a> {
+        Hash([u8; 32]),
+        Inline(&'a [u8]),
+    }
+
+    fn keccak(input: &[u8]) -> [u8; 32] {
+        let mut output = <Keccak256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Keccak256>(input, &mut output);
+        output
+    }
+
+    fn decode_length(bytes: &[u8]) -> Option<usize> {
+        if bytes.is_empty() || bytes.len() > 4 || bytes[0] == 0 {
+            return None;
+        }
+        Some(bytes.iter().fold(0usize, |acc, byte| (acc << 8) | usize::from(*byte)))
+    }
+
+    /// Decodificar el elemento RLP al inicio de `data`; devuelve (elemento, resto)
+    fn decode_item(data: &[u8]) -> Option<(Item<'_>, &[u8])> {
+        let first = *data.first()?;
+        let (is_list, offset, len) = match first {
+            0x00..=0x7f => (false, 0, 1),
+            0x80..=0xb7 => (false, 1, usize::from(first - 0x80)),
+            0xb8..=0xbf => {
+                let len_len = usize::from(first - 0xb7);
+                (false, 1 + len_len, decode_length(data.get(1..1 + len_len)?)?)
+            }
+            0xc0..=0xf7 => (true, 1, usize::from(first - 0xc0)),
+            _ => {
+                let len_len = usize::from(first - 0xf7);
+                (true, 1 + len_len, decode_length(data.get(1..1 + len_len)?)?)
+            }
+        };
+        let end = offset.checked_add(len)?;
+        let payload = data.get(offset..end)?;
+        Some((Item { is_list, payload, raw: &data[..end] }, &data[end..]))
+    }
+
+    /// Decodificar un nodo (lista RLP) en sus elementos
+    fn decode_node(data: &[u8]) -> Option<Vec<Item<'_>>> {
+        let (node, rest) = decode_item(data)?;
+        if !node.is_list || !rest.is_empty() {
+            return None;
+        }
+        let mut items = Vec::new();
+        let mut payload = node.payload;
+        while !payload.is_empty() {
+            let (item, rest) = decode_item(payload)?;
+            items.push(item);
+            payload = rest;
+        }
+        Some(items)
+    }
+
+    fn nibbles(bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+    }
+
+    /// Decodificar un path hex-prefix: (nibbles, es_hoja)
+    fn decode_path(encoded: &[u8]) -> Option<(Vec<u8>, bool)> {
+        let path = nibbles(encoded);
+        let flag = *path.first()?;
+        if flag > 3 {
+            return None;
+        }
+        let skip = if flag % 2 == 1 { 1 } else { 2 };
+        Some((path.get(skip..)?.to_vec(), flag >= 2))
+    }
+
+    fn child_ref<'a>(item: &Item<'a>) -> Result<Option<NodeRef<'a>>, Error> {
+        if item.is_list {
+            return Ok(Some(NodeRef::Inline(item.raw)));
+        }
+        match item.payload.len() {
+            0 => Ok(None),
+            32 => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(item.payload);
+                Ok(Some(NodeRef::Hash(hash)))
+            }
+            _ => Err(Error::InvalidProof),
+        }
+    }
+
+    fn value(item: &Item<'_>) -> Result<Option<Vec<u8>>, Error> {
+        if item.is_list {
+            return Err(Error::InvalidProof);
+        }
+        Ok((!item.payload.is_empty()).then(|| item.payload.to_vec()))
+    }
+
+    /// Verificar `proof` para `key` contra `root`
+    ///
+    /// Devuelve el valor almacenado (aún en RLP), `None` si el proof demuestra
+    /// que la clave no existe, o `Error::InvalidProof` si no cuadra con el root.
+    pub fn verify_proof(root: &[u8; 32], key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, Error> {
+        let path = nibbles(&keccak(key));
+        let mut path = &path[..];
+        let mut nodes = proof.iter();
+        let mut next = NodeRef::Hash(*root);
+
+        loop {
+            let node: &[u8] = match next {
+                NodeRef::Hash(hash) => {
+                    let node = nodes.next().ok_or(Error::InvalidProof)?;
+                    if keccak(node) != hash {
+                        return Err(Error::InvalidProof);
+                    }
+                    node
+                }
+                NodeRef::Inline(node) => node,
+            };
+            let items = decode_node(node).ok_or(Error::InvalidProof)?;
+
+            let child = match items.len() {
+                17 => match path.split_first() {
+                    None => return value(&items[16]),
+                    Some((nibble, rest)) => {
+                        path = rest;
+                        &items[usize::from(*nibble)]
+                    }
+                },
+                2 => {
+                    let (node_path, is_leaf) = decode_path(items[0].payload).ok_or(Error::InvalidProof)?;
+                    if is_leaf {
+                        return if path == &node_path[..] { value(&items[1]) } else { Ok(None) };
+                    }
+                    if !path.starts_with(&node_path) {
+                        return Ok(None);
+                    }
+                    path = &path[node_path.len()..];
+                    &items[1]
+                }
+                _ => return Err(Error::InvalidProof),
+            };
+
+            match child_ref(child)? {
+                Some(child) => next = child,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Extraer el `storageRoot` de una cuenta RLP `[nonce, balance, storageRoot, codeHash]`
+    pub fn decode_storage_root(account: &[u8]) -> Result<[u8; 32], Error> {
+        let items = decode_node(account).ok_or(Error::InvalidProof)?;
+        if items.len() != 4 || items[2].is_list {
+            return Err(Error::InvalidProof);
+        }
+        items[2].payload.try_into().map_err(|_| Error::InvalidProof)
+    }
+
+    /// Clave de storage de `mapping[key]` para un mapping en `slot` (layout de Solidity)
+    pub fn mapping_key(key: &[u8; 32], slot: &[u8; 32]) -> [u8; 32] {
+        let mut preimage = [0u8; 64];
+        preimage[..32].copy_from_slice(key);
+        preimage[32..].copy_from_slice(slot);
+        keccak(&preimage)
+    }
+
+    /// Decodificar un valor de storage (entero big-endian en RLP) como `u128`
+    pub fn decode_u128(value: &[u8]) -> Result<u128, Error> {
+        let (item, rest) = decode_item(value).ok_or(Error::InvalidProof)?;
+        if item.is_list || !rest.is_empty() || item.payload.len() > 16 {
+            return Err(Error::InvalidProof);
+        }
+        Ok(item.payload.iter().fold(0u128, |acc, byte| (acc << 8) | u128::from(*byte)))
+    }
+}
+
 #[ink::contract]
 mod hyperbridge_oracle {
     use ink::storage::Mapping;
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
 
+    /// Fracción de validadores (bps, 2/3) que puede aprobar un salto mayor a la desviación máxima
+    pub const SUPERMAJORITY_BPS: u128 = 6_666;
+
+    /// Rounds finalizados que se conservan por símbolo (ring buffer)
+    pub const MAX_ROUND_HISTORY: u32 = 64;
+
+    /// Envíos mínimos de un round para slashear (con dos, la mediana equidista de ambos)
+    pub const MIN_SLASH_SUBMISSIONS: usize = 3;
+
+    /// Tiempo (ms) desde el primer envío tras el cual un round sin finalizar se reabre
+    pub const ROUND_TIMEOUT: u64 = 10 * 60 * 1000;
+
+    /// State roots finalizados que se conservan por chain
+    pub const MAX_STATE_ROOTS: u32 = 64;
+
+    /// Votos (validador, root) de una altura pendiente
+    pub type StateRootVotes = Vec<(AccountId, StateRoot)>;
+
     /// Storage del contrato
     #[ink(storage)]
     pub struct HyperbridgeOracle {
         /// Precios almacenados
         prices: Mapping<String, PriceData>,
+        /// Chains registradas (id → nombre)
+        chains: Mapping<ChainId, String>,
+        /// Ids de chains registradas
+        chain_ids: Vec<ChainId>,
         /// Balances cross-chain
-        balances: Mapping<(String, AccountId), Balance>,
+        balances: Mapping<(ChainId, AccountId), Balance>,
         /// Validadores de datos
         validators: Vec<AccountId>,
         /// Umbral de validadores requeridos
         required_validators: u32,
         /// Dirección del contrato Hyperbridge (off-chain)
         hyperbridge_address: Option<String>,
+        /// Owner del oracle (puede ser un contrato de gobernanza)
+        owner: AccountId,
+        /// Owner propuesto, pendiente de aceptar
+        pending_owner: Option<AccountId>,
+        /// Último round finalizado por símbolo (0 = ninguno)
+        latest_round: Mapping<String, u32>,
+        /// Rounds de precio por (símbolo, round); se podan tras `MAX_ROUND_HISTORY`
+        rounds: Mapping<(String, u32), PriceRound>,
+        /// Historial de precios por (símbolo, round % MAX_ROUND_HISTORY)
+        round_history: Mapping<(String, u32), PriceData>,
+        /// Heartbeat y desviación máxima por símbolo
+        price_bounds: Mapping<String, PriceBounds>,
+        /// Registro de feeds por símbolo
+        feeds: Mapping<String, Feed>,
+        /// Símbolos registrados
+        feed_symbols: Vec<String>,
+        /// Parámetros de bonds y slashing
+        bond_config: BondConfig,
+        /// Bond de cada validador
+        bonds: Mapping<AccountId, ValidatorBond>,
+        /// Fondos slasheados retenidos por el contrato
+        slashed_funds: Balance,
+        /// Fee por consulta pagada (0 = consultas pagadas desactivadas)
+        query_fee: Balance,
+        /// Saldo de suscripción por consumidor
+        subscriptions: Mapping<AccountId, Balance>,
+        /// Recompensas pendientes por validador
+        validator_rewards: Mapping<AccountId, Balance>,
+        /// Total de fees cobrados
+        total_fees: Balance,
+        /// State roots finalizados por (chain, altura)
+        state_roots: Mapping<(ChainId, u64), StateRoot>,
+        /// Alturas finalizadas por chain, en orden ascendente (máx. `MAX_STATE_ROOTS`)
+        state_root_heights: Mapping<ChainId, Vec<u64>>,
+        /// Votos de validadores por (chain, altura) pendiente de finalizar
+        state_root_votes: Mapping<(ChainId, u64), StateRootVotes>,
+        /// Slot del precio en el contrato de origen, por símbolo
+        price_keys: Mapping<String, StorageSlot>,
+        /// Mapping de balances (contrato y slot base) por chain
+        balance_slots: Mapping<ChainId, StorageSlot>,
+        /// Altura del último proof de precio aceptado por símbolo
+        price_proof_heights: Mapping<String, u64>,
+        /// Altura del último proof de balance aceptado por (chain, cuenta)
+        balance_proof_heights: Mapping<(ChainId, AccountId), u64>,
+    }
+
+    /// Identificador tipado de una chain de origen
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ChainId {
+        /// Chain EVM por su chain id (EIP-155)
+        Evm(#[codec(compact)] u64),
+        /// Parachain de Substrate por su para id
+        Substrate(#[codec(compact)] u32),
     }
 
     /// Datos de precio
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct PriceData {
         /// Precio
-        price: u128,
+        pub price: u128,
         /// Timestamp
-        timestamp: u64,
+        pub timestamp: u64,
         /// Chain de origen
-        source_chain: String,
+        pub source_chain: ChainId,
         /// Contrato de origen
-        source_contract: String,
+        pub source_contract: String,
         /// Número de validadores
-        validator_count: u32,
+        pub validator_count: u32,
         /// Verificado
-        verified: bool,
+        pub verified: bool,
+        /// Round del que sale el precio
+        pub round_id: u32,
+        /// Decimales del precio (según el feed)
+        pub decimals: u8,
+    }
+
+    /// Feed registrado: qué par representa un símbolo y de dónde puede venir
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Feed {
+        /// Activo base (p. ej. "DOT")
+        pub base: String,
+        /// Moneda de cotización (p. ej. "USD")
+        pub quote: String,
+        /// Decimales del precio
+        pub decimals: u8,
+        /// Chains de origen permitidas
+        pub allowed_source_chains: Vec<ChainId>,
+    }
+
+    /// Límites de frescura y variación de un símbolo
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PriceBounds {
+        /// Antigüedad máxima del precio en ms (0 = sin límite)
+        pub heartbeat: u64,
+        /// Variación máxima entre rounds en bps (0 = sin límite)
+        pub max_deviation_bps: u16,
+    }
+
+    /// Parámetros de bonds de validadores
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct BondConfig {
+        /// Bond mínimo para enviar precios (0 = sin requisito)
+        pub min_bond: Balance,
+        /// Distancia a la mediana (bps) a partir de la cual se slashea (0 = sin slashing)
+        pub slash_threshold_bps: u16,
+        /// Porción del bond que se recorta (bps)
+        pub slash_bps: u16,
+        /// Espera en ms entre `unbond` y `withdraw_unbonded`
+        pub unbonding_period: u64,
+    }
+
+    /// Bond de un validador
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ValidatorBond {
+        /// Cantidad bondeada
+        pub bonded: Balance,
+        /// Cantidad en unbonding
+        pub unbonding: Balance,
+        /// Timestamp desde el que se puede retirar lo que está en unbonding
+        pub unlock_at: u64,
+        /// Total slasheado históricamente
+        pub slashed: Balance,
+    }
+
+    /// Estado de un validador devuelto por `get_validator_info`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ValidatorInfo {
+        /// Está en la lista de validadores
+        pub is_validator: bool,
+        /// Cumple el bond mínimo
+        pub meets_min_bond: bool,
+        /// Bond del validador
+        pub bond: ValidatorBond,
+    }
+
+    /// State root de una chain de origen
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct StateRoot {
+        /// Root del trie de estado
+        pub root: [u8; 32],
+        /// Timestamp del header en la chain de origen
+        pub timestamp: u64,
+    }
+
+    /// Payload de state proof estilo ISMP (formato de `eth_getProof`)
+    ///
+    /// `account_proof` son los nodos RLP del trie de cuentas desde el state
+    /// root de `height` hasta el contrato de origen; `storage_proof` los del
+    /// trie de storage de ese contrato hasta la hoja de `key`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct StateProof {
+        /// Altura del state root en la chain de origen
+        pub height: u64,
+        /// Clave de storage probada
+        pub key: Vec<u8>,
+        /// Nodos del proof de la cuenta del contrato
+        pub account_proof: Vec<Vec<u8>>,
+        /// Nodos del proof de storage
+        pub storage_proof: Vec<Vec<u8>>,
+    }
+
+    /// Slot de storage registrado en un contrato de origen
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct StorageSlot {
+        /// Dirección del contrato (20 bytes en hex, con o sin `0x`)
+        pub contract: String,
+        /// Clave del precio, o slot base del mapping de balances
+        pub slot: [u8; 32],
+    }
+
+    /// Round de precio abierto a envíos de validadores
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PriceRound {
+        /// Chain de origen (fijada por el primer envío)
+        source_chain: ChainId,
+        /// Contrato de origen (fijado por el primer envío)
+        source_contract: String,
+        /// Envíos (validador, precio)
+        submissions: Vec<(AccountId, u128)>,
+        /// Finalizado
+        finalized: bool,
+        /// Timestamp del primer envío
+        opened_at: u64,
+        /// Validadores fuera del umbral de slashing al finalizar (sin parte de fees)
+        outliers: Vec<AccountId>,
     }
 
     /// Eventos del contrato
@@ -52,14 +466,86 @@ mod hyperbridge_oracle {
         symbol: String,
         price: u128,
         #[ink(topic)]
-        source_chain: String,
+        source_chain: ChainId,
         verified: bool,
+        round_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct PriceSubmitted {
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        #[ink(topic)]
+        validator: AccountId,
+        price: u128,
+    }
+
+    #[ink(event)]
+    pub struct FeedSet {
+        #[ink(topic)]
+        symbol: String,
+        feed: Feed,
+    }
+
+    #[ink(event)]
+    pub struct FeedRemoved {
+        #[ink(topic)]
+        symbol: String,
+    }
+
+    #[ink(event)]
+    pub struct PriceBoundsSet {
+        #[ink(topic)]
+        symbol: String,
+        heartbeat: u64,
+        max_deviation_bps: u16,
+    }
+
+    #[ink(event)]
+    pub struct DeviationExceeded {
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        price: u128,
+        previous_price: u128,
+    }
+
+    #[ink(event)]
+    pub struct RoundForceFinalized {
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        price: u128,
+    }
+
+    #[ink(event)]
+    pub struct RoundReopened {
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        #[ink(topic)]
+        source_chain: ChainId,
+        discarded: u32,
+    }
+
+    #[ink(event)]
+    pub struct ChainRegistered {
+        #[ink(topic)]
+        chain: ChainId,
+        name: String,
+    }
+
+    #[ink(event)]
+    pub struct ChainRemoved {
+        #[ink(topic)]
+        chain: ChainId,
     }
 
     #[ink(event)]
     pub struct BalanceUpdated {
         #[ink(topic)]
-        chain: String,
+        chain: ChainId,
         #[ink(topic)]
         account: AccountId,
         balance: Balance,
@@ -71,32 +557,200 @@ mod hyperbridge_oracle {
         validator: AccountId,
     }
 
+    #[ink(event)]
+    pub struct ValidatorRemoved {
+        #[ink(topic)]
+        validator: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RequiredValidatorsChanged {
+        old_required: u32,
+        new_required: u32,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferStarted {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct BondConfigSet {
+        config: BondConfig,
+    }
+
+    #[ink(event)]
+    pub struct Bonded {
+        #[ink(topic)]
+        validator: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Unbonded {
+        #[ink(topic)]
+        validator: AccountId,
+        amount: Balance,
+        unlock_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        validator: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ValidatorSlashed {
+        #[ink(topic)]
+        validator: AccountId,
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        price: u128,
+        median: u128,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct QueryFeeSet {
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SubscriptionChanged {
+        #[ink(topic)]
+        consumer: AccountId,
+        balance: Balance,
+    }
+
+    #[ink(event)]
+    pub struct QueryFeePaid {
+        #[ink(topic)]
+        consumer: AccountId,
+        #[ink(topic)]
+        symbol: String,
+        round_id: u32,
+        fee: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ValidatorRewardsClaimed {
+        #[ink(topic)]
+        validator: AccountId,
+        amount: Balance,
+    }
+
     #[ink(event)]
     pub struct HyperbridgeAddressSet {
         #[ink(topic)]
         hyperbridge_address: String,
     }
 
+    #[ink(event)]
+    pub struct StateRootSubmitted {
+        #[ink(topic)]
+        chain: ChainId,
+        height: u64,
+        #[ink(topic)]
+        validator: AccountId,
+        root: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct StateRootFinalized {
+        #[ink(topic)]
+        chain: ChainId,
+        height: u64,
+        root: [u8; 32],
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct StateRootPruned {
+        #[ink(topic)]
+        chain: ChainId,
+        height: u64,
+    }
+
+    #[ink(event)]
+    pub struct PriceStorageKeySet {
+        #[ink(topic)]
+        symbol: String,
+        slot: StorageSlot,
+    }
+
+    #[ink(event)]
+    pub struct BalanceStorageSlotSet {
+        #[ink(topic)]
+        chain: ChainId,
+        slot: StorageSlot,
+    }
+
+    #[ink(event)]
+    pub struct StateProofVerified {
+        #[ink(topic)]
+        chain: ChainId,
+        height: u64,
+        contract: [u8; 20],
+        key: Vec<u8>,
+    }
+
     impl HyperbridgeOracle {
         /// Constructor del contrato
+        ///
+        /// El caller queda como owner.
         #[ink(constructor)]
         pub fn new(required_validators: u32) -> Self {
             Self {
                 prices: Mapping::default(),
+                chains: Mapping::default(),
+                chain_ids: Vec::new(),
                 balances: Mapping::default(),
                 validators: Vec::new(),
                 required_validators,
                 hyperbridge_address: None,
+                owner: Self::env().caller(),
+                pending_owner: None,
+                latest_round: Mapping::default(),
+                rounds: Mapping::default(),
+                round_history: Mapping::default(),
+                price_bounds: Mapping::default(),
+                feeds: Mapping::default(),
+                feed_symbols: Vec::new(),
+                bond_config: BondConfig::default(),
+                bonds: Mapping::default(),
+                slashed_funds: 0,
+                query_fee: 0,
+                subscriptions: Mapping::default(),
+                validator_rewards: Mapping::default(),
+                total_fees: 0,
+                state_roots: Mapping::default(),
+                state_root_heights: Mapping::default(),
+                state_root_votes: Mapping::default(),
+                price_keys: Mapping::default(),
+                balance_slots: Mapping::default(),
+                price_proof_heights: Mapping::default(),
+                balance_proof_heights: Mapping::default(),
             }
         }
 
-        /// Agregar un validador
+        /// Agregar un validador (solo owner)
         #[ink(message)]
         pub fn add_validator(&mut self, validator: AccountId) -> Result<(), Error> {
-            let caller = Self::env().caller();
-
-            // Solo el owner puede agregar validadores (simplificado)
-            // En producción, usar sistema de gobernanza
+            self.ensure_owner()?;
 
             if self.validators.contains(&validator) {
                 return Err(Error::ValidatorExists);
@@ -111,55 +765,107 @@ mod hyperbridge_oracle {
             Ok(())
         }
 
-        /// Actualizar precio desde otra chain (off-chain)
-        /// El frontend usa Hyperbridge SDK para obtener el precio
-        /// y luego llama a esta función para actualizarlo on-chain
+        /// Quitar un validador (solo owner)
+        ///
+        /// Falla si quedarían menos validadores que `required_validators`.
         #[ink(message)]
-        pub fn update_price(
-            &mut self,
-            symbol: String,
-            price: u128,
-            source_chain: String,
-            source_contract: String,
-            verified: bool,
-        ) -> Result<(), Error> {
-            let caller = Self::env().caller();
+        pub fn remove_validator(&mut self, validator: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
 
-            // Verificar que el caller es un validador
-            if !self.validators.contains(&caller) {
-                return Err(Error::NotValidator);
+            let index = self.validators.iter()
+                .position(|v| *v == validator)
+                .ok_or(Error::NotValidator)?;
+            if self.validators.len() <= self.required_validators as usize {
+                return Err(Error::InvalidThreshold);
             }
+            self.validators.remove(index);
 
-            let price_data = PriceData {
-                price,
-                timestamp: Self::env().block_timestamp(),
-                source_chain: source_chain.clone(),
-                source_contract,
-                validator_count: 1,
-                verified,
-            };
-
-            self.prices.insert(symbol.clone(), &price_data);
-
-            Self::env().emit_event(PriceUpdated {
-                symbol,
-                price,
-                source_chain,
-                verified,
+            Self::env().emit_event(ValidatorRemoved {
+                validator,
             });
 
             Ok(())
         }
 
-        /// Actualizar balance desde otra chain (off-chain)
-        /// El frontend usa Hyperbridge SDK para obtener el balance
-        /// y luego llama a esta función para actualizarlo on-chain
+        /// Cambiar el número de validadores requeridos (solo owner)
+        ///
+        /// Debe estar entre 1 y el número de validadores registrados.
         #[ink(message)]
-        pub fn update_balance(
-            &mut self,
-            chain: String,
-            account: AccountId,
-            balance: Balance,
+        pub fn set_required_validators(&mut self, required_validators: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if required_validators == 0 || required_validators as usize > self.validators.len() {
+                return Err(Error::InvalidThreshold);
+            }
+
+            let old_required = self.required_validators;
+            self.required_validators = required_validators;
+
+            Self::env().emit_event(RequiredValidatorsChanged {
+                old_required,
+                new_required: required_validators,
+            });
+
+            Ok(())
+        }
+
+        /// Proponer un nuevo owner (solo owner)
+        ///
+        /// El cambio se completa cuando `new_owner` llama a `accept_ownership`.
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.pending_owner = Some(new_owner);
+
+            Self::env().emit_event(OwnershipTransferStarted {
+                previous_owner: self.owner,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Aceptar el ownership propuesto
+        #[ink(message)]
+        pub fn accept_ownership(&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+
+            if self.pending_owner != Some(caller) {
+                return Err(Error::NotOwner);
+            }
+
+            let previous_owner = self.owner;
+            self.owner = caller;
+            self.pending_owner = None;
+
+            Self::env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Enviar el precio de un validador para (símbolo, round)
+        /// El frontend usa Hyperbridge SDK para obtener el precio
+        /// y luego llama a esta función para enviarlo on-chain.
+        ///
+        /// Solo se acepta el round abierto (`get_current_round`). Al llegar a
+        /// `required_validators` envíos distintos se finaliza con la mediana,
+        /// salvo que supere la desviación máxima: entonces el round sigue
+        /// abierto hasta que lo respalde una supermayoría de validadores.
+        /// Pasado `ROUND_TIMEOUT` sin finalizar, el siguiente envío descarta
+        /// los anteriores y reabre el round con su propia fuente. Si el feed
+        /// tiene contrato registrado para proofs, `source_contract` debe serlo.
+        #[ink(message)]
+        pub fn update_price(
+            &mut self,
+            symbol: String,
+            round_id: u32,
+            price: u128,
+            source_chain: ChainId,
+            source_contract: String,
         ) -> Result<(), Error> {
             let caller = Self::env().caller();
 
@@ -168,96 +874,2544 @@ mod hyperbridge_oracle {
                 return Err(Error::NotValidator);
             }
 
-            self.balances.insert((chain.clone(), account), &balance);
+            if self.bonds.get(caller).unwrap_or_default().bonded < self.bond_config.min_bond {
+                return Err(Error::InsufficientBond);
+            }
 
-            Self::env().emit_event(BalanceUpdated {
-                chain,
-                account,
-                balance,
+            if price == 0 {
+                return Err(Error::InvalidPrice);
+            }
+
+            self.ensure_chain(source_chain)?;
+            let feed = self.feeds.get(symbol.clone()).ok_or(Error::FeedNotFound)?;
+            if !feed.allowed_source_chains.contains(&source_chain) {
+                return Err(Error::InvalidChain);
+            }
+
+            // Con proofs registrados, la mediana lee del mismo contrato
+            if let Some(registered) = self.price_keys.get(symbol.clone()) {
+                if Self::parse_address(&source_contract).ok() != Some(Self::parse_address(&registered.contract)?) {
+                    return Err(Error::SourceMismatch);
+                }
+            }
+
+            if round_id != self.get_current_round(symbol.clone()) {
+                return Err(Error::InvalidRound);
+            }
+
+            let mut round = self.open_round(symbol.clone(), round_id, source_chain, &source_contract);
+
+            if round.source_chain != source_chain || !Self::same_contract(&round.source_contract, &source_contract) {
+                return Err(Error::SourceMismatch);
+            }
+
+            if round.submissions.iter().any(|(validator, _)| *validator == caller) {
+                return Err(Error::DuplicateSubmission);
+            }
+
+            round.submissions.push((caller, price));
+
+            Self::env().emit_event(PriceSubmitted {
+                symbol: symbol.clone(),
+                round_id,
+                validator: caller,
+                price,
             });
 
+            if round.submissions.len() >= self.required_validators as usize {
+                self.try_finalize_round(symbol.clone(), round_id, &mut round)?;
+            }
+
+            self.rounds.insert((symbol, round_id), &round);
+
             Ok(())
         }
 
-        /// Establecer dirección de Hyperbridge (off-chain)
+        /// Finalizar con la mediana un round bloqueado por desviación (solo owner)
+        ///
+        /// Para saltos reales de mercado sin supermayoría activa: el round debe
+        /// ser el abierto y tener al menos `required_validators` envíos.
         #[ink(message)]
-        pub fn set_hyperbridge_address(&mut self, hyperbridge_address: String) -> Result<(), Error> {
+        pub fn force_finalize_round(&mut self, symbol: String, round_id: u32) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if round_id != self.get_current_round(symbol.clone()) {
+                return Err(Error::InvalidRound);
+            }
+
+            let mut round = self.rounds.get((symbol.clone(), round_id)).ok_or(Error::InvalidRound)?;
+            if round.submissions.len() < self.required_validators as usize {
+                return Err(Error::InvalidRound);
+            }
+
+            let mut prices: Vec<u128> = round.submissions.iter().map(|(_, price)| *price).collect();
+            let price = Self::median(&mut prices).ok_or(Error::InvalidPrice)?;
+
+            round.outliers = self.slash_outliers(&symbol, round_id, &round.submissions, price)?;
+            self.finalize_round(symbol.clone(), round_id, &mut round, price, Self::env().block_timestamp());
+            self.rounds.insert((symbol.clone(), round_id), &round);
+
+            Self::env().emit_event(RoundForceFinalized {
+                symbol,
+                round_id,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Configurar bonds y slashing (solo owner)
+        #[ink(message)]
+        pub fn set_bond_config(&mut self, config: BondConfig) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if config.slash_bps > 10_000 {
+                return Err(Error::InvalidThreshold);
+            }
+
+            self.bond_config = config;
+
+            Self::env().emit_event(BondConfigSet {
+                config,
+            });
+
+            Ok(())
+        }
+
+        /// Depositar bond en token nativo
+        #[ink(message, payable)]
+        pub fn bond(&mut self) -> Result<(), Error> {
             let caller = Self::env().caller();
-            self.hyperbridge_address = Some(hyperbridge_address.clone());
+            let amount = Self::env().transferred_value();
 
-            Self::env().emit_event(HyperbridgeAddressSet {
-                hyperbridge_address,
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            let mut bond = self.bonds.get(caller).unwrap_or_default();
+            bond.bonded = bond.bonded
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.bonds.insert(caller, &bond);
+
+            Self::env().emit_event(Bonded {
+                validator: caller,
+                amount,
             });
 
             Ok(())
         }
 
-        /// Obtener precio de un símbolo
+        /// Iniciar la salida de parte del bond
+        ///
+        /// Reinicia la espera de todo lo que esté en unbonding.
         #[ink(message)]
-        pub fn get_price(&self, symbol: String) -> Option<PriceData> {
-            self.prices.get(symbol)
+        pub fn unbond(&mut self, amount: Balance) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut bond = self.bonds.get(caller).ok_or(Error::InsufficientBond)?;
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            bond.bonded = bond.bonded
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientBond)?;
+            bond.unbonding = bond.unbonding
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            bond.unlock_at = Self::env().block_timestamp()
+                .checked_add(self.bond_config.unbonding_period)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.bonds.insert(caller, &bond);
+
+            Self::env().emit_event(Unbonded {
+                validator: caller,
+                amount,
+                unlock_at: bond.unlock_at,
+            });
+
+            Ok(())
         }
 
-        /// Obtener balance cross-chain
+        /// Retirar lo que terminó el período de unbonding
         #[ink(message)]
-        pub fn get_balance(&self, chain: String, account: AccountId) -> Option<Balance> {
-            self.balances.get((chain, account))
+        pub fn withdraw_unbonded(&mut self) -> Result<Balance, Error> {
+            let caller = Self::env().caller();
+            let mut bond = self.bonds.get(caller).ok_or(Error::NothingToWithdraw)?;
+
+            if bond.unbonding == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            if Self::env().block_timestamp() < bond.unlock_at {
+                return Err(Error::UnbondingActive);
+            }
+
+            let amount = bond.unbonding;
+            bond.unbonding = 0;
+            self.bonds.insert(caller, &bond);
+
+            Self::env().transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Self::env().emit_event(Withdrawn {
+                validator: caller,
+                amount,
+            });
+
+            Ok(amount)
         }
 
-        /// Obtener lista de validadores
+        /// Retirar los fondos slasheados (solo owner)
         #[ink(message)]
-        pub fn get_validators(&self) -> Vec<AccountId> {
-            self.validators.clone()
+        pub fn withdraw_slashed(&mut self, to: AccountId) -> Result<Balance, Error> {
+            self.ensure_owner()?;
+
+            let amount = self.slashed_funds;
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.slashed_funds = 0;
+            Self::env().transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Ok(amount)
         }
 
-        /// Obtener dirección de Hyperbridge
+        /// Obtener el estado de un validador
         #[ink(message)]
-        pub fn get_hyperbridge_address(&self) -> Option<String> {
-            self.hyperbridge_address.clone()
+        pub fn get_validator_info(&self, validator: AccountId) -> ValidatorInfo {
+            let bond = self.bonds.get(validator).unwrap_or_default();
+            ValidatorInfo {
+                is_validator: self.validators.contains(&validator),
+                meets_min_bond: bond.bonded >= self.bond_config.min_bond,
+                bond,
+            }
+        }
+
+        /// Obtener la configuración de bonds
+        #[ink(message)]
+        pub fn get_bond_config(&self) -> BondConfig {
+            self.bond_config
+        }
+
+        /// Obtener los fondos slasheados retenidos
+        #[ink(message)]
+        pub fn get_slashed_funds(&self) -> Balance {
+            self.slashed_funds
         }
 
-        /// Verificar precio con múltiples validadores
+        /// Fijar el fee por consulta pagada (solo owner)
         #[ink(message)]
-        pub fn verify_price(
+        pub fn set_query_fee(&mut self, fee: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            self.query_fee = fee;
+
+            Self::env().emit_event(QueryFeeSet {
+                fee,
+            });
+
+            Ok(())
+        }
+
+        /// Depositar saldo de suscripción para consultas pagadas
+        #[ink(message, payable)]
+        pub fn deposit_subscription(&mut self) -> Result<Balance, Error> {
+            let consumer = Self::env().caller();
+            let amount = Self::env().transferred_value();
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            let balance = self.subscriptions.get(consumer).unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.subscriptions.insert(consumer, &balance);
+
+            Self::env().emit_event(SubscriptionChanged {
+                consumer,
+                balance,
+            });
+
+            Ok(balance)
+        }
+
+        /// Retirar saldo de suscripción
+        #[ink(message)]
+        pub fn withdraw_subscription(&mut self, amount: Balance) -> Result<Balance, Error> {
+            let consumer = Self::env().caller();
+
+            let balance = self.subscriptions.get(consumer).unwrap_or(0)
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientFee)?;
+            self.subscriptions.insert(consumer, &balance);
+
+            Self::env().transfer(consumer, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Self::env().emit_event(SubscriptionChanged {
+                consumer,
+                balance,
+            });
+
+            Ok(balance)
+        }
+
+        /// Consultar el precio fresco pagando el fee
+        ///
+        /// Se cobra primero del valor transferido (el excedente va a la
+        /// suscripción) y, si no se transfiere nada, de la suscripción. El fee
+        /// se reparte entre los validadores del round consultado.
+        #[ink(message, payable)]
+        pub fn query_price(&mut self, symbol: String) -> Result<PriceData, Error> {
+            let consumer = Self::env().caller();
+            let paid = Self::env().transferred_value();
+            let fee = self.query_fee;
+
+            let price_data = self.get_fresh_price(symbol.clone())?;
+
+            let mut balance = self.subscriptions.get(consumer).unwrap_or(0)
+                .checked_add(paid)
+                .ok_or(Error::ArithmeticOverflow)?;
+            balance = balance.checked_sub(fee).ok_or(Error::InsufficientFee)?;
+            if paid > 0 || fee > 0 {
+                self.subscriptions.insert(consumer, &balance);
+            }
+
+            if fee > 0 {
+                self.distribute_fee(&symbol, price_data.round_id, fee)?;
+
+                Self::env().emit_event(QueryFeePaid {
+                    consumer,
+                    symbol,
+                    round_id: price_data.round_id,
+                    fee,
+                });
+            }
+
+            Ok(price_data)
+        }
+
+        /// Reclamar las recompensas acumuladas por fees
+        #[ink(message)]
+        pub fn claim_validator_rewards(&mut self) -> Result<Balance, Error> {
+            let validator = Self::env().caller();
+
+            let amount = self.validator_rewards.get(validator).unwrap_or(0);
+            if amount == 0 {
+                return Err(Error::NothingToWithdraw);
+            }
+
+            self.validator_rewards.remove(validator);
+
+            Self::env().transfer(validator, amount)
+                .map_err(|_| Error::TransferFailed)?;
+
+            Self::env().emit_event(ValidatorRewardsClaimed {
+                validator,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Obtener las recompensas pendientes de un validador
+        #[ink(message)]
+        pub fn get_validator_rewards(&self, validator: AccountId) -> Balance {
+            self.validator_rewards.get(validator).unwrap_or(0)
+        }
+
+        /// Obtener el saldo de suscripción de un consumidor
+        #[ink(message)]
+        pub fn get_subscription_balance(&self, consumer: AccountId) -> Balance {
+            self.subscriptions.get(consumer).unwrap_or(0)
+        }
+
+        /// Obtener el fee por consulta
+        #[ink(message)]
+        pub fn get_query_fee(&self) -> Balance {
+            self.query_fee
+        }
+
+        /// Obtener el total de fees cobrados
+        #[ink(message)]
+        pub fn get_total_fees(&self) -> Balance {
+            self.total_fees
+        }
+
+        /// Enviar el state root de una chain de origen a una altura
+        ///
+        /// Se finaliza cuando el mismo root y timestamp lo envía una supermayoría
+        /// de validadores, y nunca menos de `required_validators`. Al superar `MAX_STATE_ROOTS` alturas finalizadas
+        /// se poda la más antigua; alturas anteriores a esa ya no se aceptan.
+        #[ink(message)]
+        pub fn submit_state_root(
             &mut self,
-            symbol: String,
-            price: u128,
-            source_chain: String,
-            source_contract: String,
-        ) -> Result<bool, Error> {
-            // En producción, agregar lógica de verificación
-            // con múltiples validadores y consenso
+            chain: ChainId,
+            height: u64,
+            root: [u8; 32],
+            timestamp: u64,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
 
-            let price_data = PriceData {
-                price,
-                timestamp: Self::env().block_timestamp(),
-                source_chain: source_chain.clone(),
-                source_contract,
-                validator_count: self.validators.len() as u32,
-                verified: self.validators.len() >= self.required_validators as usize,
-            };
+            if !self.validators.contains(&caller) {
+                return Err(Error::NotValidator);
+            }
 
-            self.prices.insert(symbol.clone(), &price_data);
+            if self.bonds.get(caller).unwrap_or_default().bonded < self.bond_config.min_bond {
+                return Err(Error::InsufficientBond);
+            }
 
-            Self::env().emit_event(PriceUpdated {
-                symbol,
-                price,
-                source_chain,
-                verified: price_data.verified,
+            self.ensure_chain(chain)?;
+            if self.state_roots.contains((chain, height)) {
+                return Err(Error::StateRootFinalized);
+            }
+
+            let heights = self.state_root_heights.get(chain).unwrap_or_default();
+            if heights.len() >= MAX_STATE_ROOTS as usize && heights.first().is_some_and(|oldest| height < *oldest) {
+                return Err(Error::StaleStateRoot);
+            }
+
+            let mut votes = self.state_root_votes.get((chain, height)).unwrap_or_default();
+            if votes.iter().any(|(validator, _)| *validator == caller) {
+                return Err(Error::DuplicateSubmission);
+            }
+
+            let state_root = StateRoot { root, timestamp };
+            votes.push((caller, state_root));
+
+            Self::env().emit_event(StateRootSubmitted {
+                chain,
+                height,
+                validator: caller,
+                root,
             });
 
-            Ok(price_data.verified)
+            let agreeing = votes.iter().filter(|(_, vote)| *vote == state_root).count();
+            if agreeing >= self.required_validators as usize && self.is_supermajority(agreeing)? {
+                self.state_root_votes.remove((chain, height));
+                self.finalize_state_root(chain, height, state_root, heights);
+            } else {
+                self.state_root_votes.insert((chain, height), &votes);
+            }
+
+            Ok(())
         }
-    }
 
-    /// Errores del contrato
-    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub enum Error {
-        ValidatorExists,
-        NotValidator,
-        InvalidPrice,
-        InvalidChain,
+        /// Obtener el state root finalizado de una chain a una altura
+        #[ink(message)]
+        pub fn get_state_root(&self, chain: ChainId, height: u64) -> Option<StateRoot> {
+            self.state_roots.get((chain, height))
+        }
+
+        /// Última altura finalizada de una chain
+        #[ink(message)]
+        pub fn get_latest_height(&self, chain: ChainId) -> Option<u64> {
+            self.state_root_heights.get(chain).and_then(|heights| heights.last().copied())
+        }
+
+        /// Alturas finalizadas (no podadas) de una chain
+        #[ink(message)]
+        pub fn get_state_root_heights(&self, chain: ChainId) -> Vec<u64> {
+            self.state_root_heights.get(chain).unwrap_or_default()
+        }
+
+        /// Votos pendientes para (chain, altura)
+        #[ink(message)]
+        pub fn get_state_root_votes(&self, chain: ChainId, height: u64) -> StateRootVotes {
+            self.state_root_votes.get((chain, height)).unwrap_or_default()
+        }
+
+        /// Registrar el contrato y la clave de storage del precio de un feed (solo owner)
+        ///
+        /// Es lo que deben probar los proofs de `update_price_with_proof`.
+        #[ink(message)]
+        pub fn set_price_storage_key(&mut self, symbol: String, slot: StorageSlot) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if !self.feeds.contains(symbol.clone()) {
+                return Err(Error::FeedNotFound);
+            }
+            Self::parse_address(&slot.contract)?;
+
+            self.price_keys.insert(symbol.clone(), &slot);
+
+            Self::env().emit_event(PriceStorageKeySet { symbol, slot });
+
+            Ok(())
+        }
+
+        /// Obtener el contrato y la clave de storage del precio de un feed
+        #[ink(message)]
+        pub fn get_price_storage_key(&self, symbol: String) -> Option<StorageSlot> {
+            self.price_keys.get(symbol)
+        }
+
+        /// Registrar el token y el slot base de su mapping de balances en una chain (solo owner)
+        ///
+        /// La clave de la cuenta `a` es `keccak(a ‖ slot)`; la cuenta ocupa los
+        /// 32 bytes (una dirección H160 va con ceros a la izquierda).
+        #[ink(message)]
+        pub fn set_balance_storage_slot(&mut self, chain: ChainId, slot: StorageSlot) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_chain(chain)?;
+            Self::parse_address(&slot.contract)?;
+
+            self.balance_slots.insert(chain, &slot);
+
+            Self::env().emit_event(BalanceStorageSlotSet { chain, slot });
+
+            Ok(())
+        }
+
+        /// Obtener el token y el slot base de balances de una chain
+        #[ink(message)]
+        pub fn get_balance_storage_slot(&self, chain: ChainId) -> Option<StorageSlot> {
+            self.balance_slots.get(chain)
+        }
+
+        /// Finalizar el round abierto con un precio probado por state proof
+        ///
+        /// `source_contract` debe ser el contrato registrado del feed y el proof
+        /// cubrir su clave, verificándose contra el state root de `source_chain`
+        /// a `proof.height`, que debe ser mayor que la del último proof aceptado.
+        /// El precio probado sustituye a la mediana y toma el timestamp del root,
+        /// que no puede superar el heartbeat del feed ni ser anterior al precio
+        /// vigente; los envíos previos del round se conservan para el reparto
+        /// de fees.
+        #[ink(message)]
+        pub fn update_price_with_proof(
+            &mut self,
+            symbol: String,
+            source_chain: ChainId,
+            source_contract: String,
+            proof: StateProof,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+
+            if !self.validators.contains(&caller) {
+                return Err(Error::NotValidator);
+            }
+
+            if self.bonds.get(caller).unwrap_or_default().bonded < self.bond_config.min_bond {
+                return Err(Error::InsufficientBond);
+            }
+
+            self.ensure_chain(source_chain)?;
+            let feed = self.feeds.get(symbol.clone()).ok_or(Error::FeedNotFound)?;
+            if !feed.allowed_source_chains.contains(&source_chain) {
+                return Err(Error::InvalidChain);
+            }
+
+            let registered = self.price_keys.get(symbol.clone()).ok_or(Error::StorageSlotNotSet)?;
+            let contract = Self::parse_address(&registered.contract)?;
+            if Self::parse_address(&source_contract)? != contract {
+                return Err(Error::SourceMismatch);
+            }
+            if proof.key != registered.slot {
+                return Err(Error::InvalidProof);
+            }
+
+            if let Some(last_height) = self.price_proof_heights.get(symbol.clone()) {
+                if proof.height <= last_height {
+                    return Err(Error::StaleProof);
+                }
+            }
+
+            let (price, root_timestamp) = self.verify_state_proof(source_chain, contract, &proof)?;
+            if price == 0 {
+                return Err(Error::InvalidPrice);
+            }
+
+            // El precio vale lo que el header del root, no el bloque actual
+            let now = Self::env().block_timestamp();
+            let timestamp = root_timestamp.min(now);
+            let heartbeat = self.price_bounds.get(symbol.clone())
+                .map(|bounds| bounds.heartbeat)
+                .unwrap_or(0);
+            if heartbeat > 0 && now - timestamp > heartbeat {
+                return Err(Error::StalePrice);
+            }
+
+            // Un root anterior al precio vigente lo haría retroceder
+            if let Some(current) = self.prices.get(symbol.clone()) {
+                if timestamp < current.timestamp {
+                    return Err(Error::StalePrice);
+                }
+            }
+
+            let round_id = self.get_current_round(symbol.clone());
+            let mut round = self.open_round(symbol.clone(), round_id, source_chain, &source_contract);
+
+            if round.source_chain != source_chain
+                || Self::parse_address(&round.source_contract).ok() != Some(contract)
+            {
+                return Err(Error::SourceMismatch);
+            }
+
+            if !round.submissions.iter().any(|(validator, _)| *validator == caller) {
+                round.submissions.push((caller, price));
+            }
+
+            self.price_proof_heights.insert(symbol.clone(), &proof.height);
+            self.finalize_round(symbol.clone(), round_id, &mut round, price, timestamp);
+            self.rounds.insert((symbol, round_id), &round);
+
+            Ok(())
+        }
+
+        /// Actualizar un balance cross-chain probado por state proof
+        ///
+        /// `proof.key` debe ser la clave de `account` en el mapping de balances
+        /// registrado para la chain; proofs de alturas no posteriores a la
+        /// última aceptada para (chain, cuenta) se rechazan.
+        #[ink(message)]
+        pub fn update_balance_with_proof(
+            &mut self,
+            chain: ChainId,
+            account: AccountId,
+            proof: StateProof,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+
+            if !self.validators.contains(&caller) {
+                return Err(Error::NotValidator);
+            }
+
+            if self.bonds.get(caller).unwrap_or_default().bonded < self.bond_config.min_bond {
+                return Err(Error::InsufficientBond);
+            }
+
+            self.ensure_chain(chain)?;
+            let registered = self.balance_slots.get(chain).ok_or(Error::StorageSlotNotSet)?;
+            let contract = Self::parse_address(&registered.contract)?;
+            let account_key: &[u8; 32] = account.as_ref();
+            let expected_key = crate::trie::mapping_key(account_key, &registered.slot);
+            if proof.key != expected_key {
+                return Err(Error::InvalidProof);
+            }
+
+            if let Some(last_height) = self.balance_proof_heights.get((chain, account)) {
+                if proof.height <= last_height {
+                    return Err(Error::StaleProof);
+                }
+            }
+
+            let (balance, _) = self.verify_state_proof(chain, contract, &proof)?;
+
+            self.balance_proof_heights.insert((chain, account), &proof.height);
+            self.balances.insert((chain, account), &balance);
+
+            Self::env().emit_event(BalanceUpdated {
+                chain,
+                account,
+                balance,
+            });
+
+            Ok(())
+        }
+
+        /// Registrar o renombrar una chain de origen (solo owner)
+        #[ink(message)]
+        pub fn register_chain(&mut self, chain: ChainId, name: String) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if name.is_empty() {
+                return Err(Error::InvalidChain);
+            }
+
+            if !self.chain_ids.contains(&chain) {
+                self.chain_ids.push(chain);
+            }
+            self.chains.insert(chain, &name);
+
+            Self::env().emit_event(ChainRegistered { chain, name });
+
+            Ok(())
+        }
+
+        /// Quitar una chain registrada (solo owner)
+        ///
+        /// Los datos guardados se conservan, pero dejan de aceptarse updates
+        /// y consultas de balance para esa chain.
+        #[ink(message)]
+        pub fn remove_chain(&mut self, chain: ChainId) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let index = self.chain_ids.iter()
+                .position(|registered| *registered == chain)
+                .ok_or(Error::InvalidChain)?;
+            self.chain_ids.remove(index);
+            self.chains.remove(chain);
+
+            Self::env().emit_event(ChainRemoved { chain });
+
+            Ok(())
+        }
+
+        /// Nombre de una chain registrada
+        #[ink(message)]
+        pub fn get_chain(&self, chain: ChainId) -> Option<String> {
+            self.chains.get(chain)
+        }
+
+        /// Listar chains registradas
+        #[ink(message)]
+        pub fn list_chains(&self) -> Vec<(ChainId, String)> {
+            self.chain_ids.iter()
+                .filter_map(|chain| self.chains.get(chain).map(|name| (*chain, name)))
+                .collect()
+        }
+
+        /// Establecer dirección de Hyperbridge (off-chain, solo owner)
+        #[ink(message)]
+        pub fn set_hyperbridge_address(&mut self, hyperbridge_address: String) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.hyperbridge_address = Some(hyperbridge_address.clone());
+
+            Self::env().emit_event(HyperbridgeAddressSet {
+                hyperbridge_address,
+            });
+
+            Ok(())
+        }
+
+        /// Registrar o actualizar un feed (solo owner)
+        #[ink(message)]
+        pub fn set_feed(
+            &mut self,
+            symbol: String,
+            base: String,
+            quote: String,
+            decimals: u8,
+            allowed_source_chains: Vec<ChainId>,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if symbol.is_empty() || base.is_empty() || quote.is_empty() || allowed_source_chains.is_empty() {
+                return Err(Error::InvalidFeed);
+            }
+
+            for chain in &allowed_source_chains {
+                self.ensure_chain(*chain)?;
+            }
+
+            let feed = Feed {
+                base,
+                quote,
+                decimals,
+                allowed_source_chains,
+            };
+
+            if !self.feed_symbols.contains(&symbol) {
+                self.feed_symbols.push(symbol.clone());
+            }
+            self.feeds.insert(symbol.clone(), &feed);
+
+            Self::env().emit_event(FeedSet {
+                symbol,
+                feed,
+            });
+
+            Ok(())
+        }
+
+        /// Quitar un feed (solo owner); el último precio sigue consultable
+        #[ink(message)]
+        pub fn remove_feed(&mut self, symbol: String) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            let index = self.feed_symbols.iter()
+                .position(|s| *s == symbol)
+                .ok_or(Error::FeedNotFound)?;
+            self.feed_symbols.remove(index);
+            self.feeds.remove(symbol.clone());
+
+            Self::env().emit_event(FeedRemoved {
+                symbol,
+            });
+
+            Ok(())
+        }
+
+        /// Obtener un feed
+        #[ink(message)]
+        pub fn get_feed(&self, symbol: String) -> Option<Feed> {
+            self.feeds.get(symbol)
+        }
+
+        /// Listar los feeds registrados
+        #[ink(message)]
+        pub fn list_feeds(&self) -> Vec<(String, Feed)> {
+            self.feed_symbols.iter()
+                .filter_map(|symbol| self.feeds.get(symbol).map(|feed| (symbol.clone(), feed)))
+                .collect()
+        }
+
+        /// Configurar heartbeat y desviación máxima de un símbolo (solo owner)
+        #[ink(message)]
+        pub fn set_price_bounds(
+            &mut self,
+            symbol: String,
+            heartbeat: u64,
+            max_deviation_bps: u16,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            if max_deviation_bps > 10_000 {
+                return Err(Error::InvalidThreshold);
+            }
+
+            if self.feeds.get(symbol.clone()).is_none() {
+                return Err(Error::FeedNotFound);
+            }
+
+            self.price_bounds.insert(symbol.clone(), &PriceBounds {
+                heartbeat,
+                max_deviation_bps,
+            });
+
+            Self::env().emit_event(PriceBoundsSet {
+                symbol,
+                heartbeat,
+                max_deviation_bps,
+            });
+
+            Ok(())
+        }
+
+        /// Obtener los límites de un símbolo
+        #[ink(message)]
+        pub fn get_price_bounds(&self, symbol: String) -> Option<PriceBounds> {
+            self.price_bounds.get(symbol)
+        }
+
+        /// Obtener precio de un símbolo
+        #[ink(message)]
+        pub fn get_price(&self, symbol: String) -> Option<PriceData> {
+            self.prices.get(symbol)
+        }
+
+        /// Obtener el precio de un round finalizado (solo los últimos `MAX_ROUND_HISTORY`)
+        #[ink(message)]
+        pub fn get_round(&self, symbol: String, round_id: u32) -> Option<PriceData> {
+            self.round_history.get((symbol, round_id % MAX_ROUND_HISTORY))
+                .filter(|price_data| price_data.round_id == round_id)
+        }
+
+        /// Obtener el último round finalizado de un símbolo
+        #[ink(message)]
+        pub fn get_latest_round_id(&self, symbol: String) -> Option<u32> {
+            self.latest_round.get(symbol)
+        }
+
+        /// Precio promedio ponderado por tiempo de los últimos `window` ms
+        ///
+        /// Cada round vale desde su timestamp hasta el siguiente (el último,
+        /// hasta ahora). Falla si el historial no cubre la ventana completa.
+        #[ink(message)]
+        pub fn get_twap(&self, symbol: String, window: u64) -> Result<u128, Error> {
+            if window == 0 {
+                return Err(Error::InvalidWindow);
+            }
+
+            let mut round_id = self.latest_round.get(symbol.clone())
+                .ok_or(Error::PriceNotFound)?;
+
+            let now = Self::env().block_timestamp();
+            let start = now.checked_sub(window).ok_or(Error::InsufficientHistory)?;
+
+            let mut end = now;
+            let mut weighted: u128 = 0;
+            loop {
+                let price_data = self.get_round(symbol.clone(), round_id)
+                    .ok_or(Error::InsufficientHistory)?;
+
+                let segment_start = price_data.timestamp.max(start);
+                let duration = end.saturating_sub(segment_start);
+                let value = price_data.price
+                    .checked_mul(u128::from(duration))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                weighted = weighted
+                    .checked_add(value)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                if price_data.timestamp <= start {
+                    break;
+                }
+
+                end = price_data.timestamp;
+                round_id = round_id.checked_sub(1)
+                    .filter(|id| *id > 0)
+                    .ok_or(Error::InsufficientHistory)?;
+            }
+
+            Ok(weighted / u128::from(window))
+        }
+
+        /// Obtener precio de un símbolo si está dentro de su heartbeat
+        #[ink(message)]
+        pub fn get_fresh_price(&self, symbol: String) -> Result<PriceData, Error> {
+            let price_data = self.prices.get(symbol.clone())
+                .ok_or(Error::PriceNotFound)?;
+
+            let heartbeat = self.price_bounds.get(symbol)
+                .map(|bounds| bounds.heartbeat)
+                .unwrap_or(0);
+            let age = Self::env().block_timestamp().saturating_sub(price_data.timestamp);
+            if heartbeat > 0 && age > heartbeat {
+                return Err(Error::StalePrice);
+            }
+
+            Ok(price_data)
+        }
+
+        /// Obtener balance cross-chain (la chain debe estar registrada)
+        #[ink(message)]
+        pub fn get_balance(&self, chain: ChainId, account: AccountId) -> Result<Option<Balance>, Error> {
+            self.ensure_chain(chain)?;
+            Ok(self.balances.get((chain, account)))
+        }
+
+        /// Obtener lista de validadores
+        #[ink(message)]
+        pub fn get_validators(&self) -> Vec<AccountId> {
+            self.validators.clone()
+        }
+
+        /// Obtener dirección de Hyperbridge
+        #[ink(message)]
+        pub fn get_hyperbridge_address(&self) -> Option<String> {
+            self.hyperbridge_address.clone()
+        }
+
+        /// Obtener el owner
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Obtener el owner pendiente de aceptar
+        #[ink(message)]
+        pub fn get_pending_owner(&self) -> Option<AccountId> {
+            self.pending_owner
+        }
+
+        /// Obtener el número de validadores requeridos
+        #[ink(message)]
+        pub fn get_required_validators(&self) -> u32 {
+            self.required_validators
+        }
+
+        /// Verificar si un round alcanzó consenso (rounds podados dan `InvalidRound`)
+        #[ink(message)]
+        pub fn verify_price(&self, symbol: String, round_id: u32) -> Result<bool, Error> {
+            self.rounds.get((symbol, round_id))
+                .map(|round| round.finalized)
+                .ok_or(Error::InvalidRound)
+        }
+
+        /// Obtener el round abierto de un símbolo
+        #[ink(message)]
+        pub fn get_current_round(&self, symbol: String) -> u32 {
+            self.latest_round.get(symbol).unwrap_or(0).saturating_add(1)
+        }
+
+        /// Obtener los envíos de un round
+        #[ink(message)]
+        pub fn get_round_submissions(&self, symbol: String, round_id: u32) -> Vec<(AccountId, u128)> {
+            self.rounds.get((symbol, round_id))
+                .map(|round| round.submissions)
+                .unwrap_or_default()
+        }
+
+        /// Cargar el round abierto de `symbol` o crearlo para la fuente dada
+        ///
+        /// Si el round lleva `ROUND_TIMEOUT` sin finalizar se descartan sus
+        /// envíos, para que una fuente sin apoyo no lo bloquee para siempre.
+        fn open_round(
+            &self,
+            symbol: String,
+            round_id: u32,
+            source_chain: ChainId,
+            source_contract: &str,
+        ) -> PriceRound {
+            let now = Self::env().block_timestamp();
+            let fresh = PriceRound {
+                source_chain,
+                source_contract: String::from(source_contract),
+                submissions: Vec::new(),
+                finalized: false,
+                opened_at: now,
+                outliers: Vec::new(),
+            };
+
+            match self.rounds.get((symbol.clone(), round_id)) {
+                None => fresh,
+                Some(round) if now.saturating_sub(round.opened_at) < ROUND_TIMEOUT => round,
+                Some(round) => {
+                    Self::env().emit_event(RoundReopened {
+                        symbol,
+                        round_id,
+                        source_chain,
+                        discarded: round.submissions.len() as u32,
+                    });
+                    fresh
+                }
+            }
+        }
+
+        /// Finalizar un round con la mediana de sus envíos
+        ///
+        /// Si la mediana se aleja del precio anterior más de lo permitido y no
+        /// hay supermayoría, el round queda abierto y se emite `DeviationExceeded`.
+        fn try_finalize_round(
+            &mut self,
+            symbol: String,
+            round_id: u32,
+            round: &mut PriceRound,
+        ) -> Result<(), Error> {
+            let mut prices: Vec<u128> = round.submissions.iter().map(|(_, price)| *price).collect();
+            let price = Self::median(&mut prices).ok_or(Error::InvalidPrice)?;
+
+            if let Some(previous) = self.prices.get(symbol.clone()) {
+                let within = self.within_deviation(&symbol, previous.price, price)?;
+                if !within && !self.is_supermajority(round.submissions.len())? {
+                    Self::env().emit_event(DeviationExceeded {
+                        symbol,
+                        round_id,
+                        price,
+                        previous_price: previous.price,
+                    });
+                    return Ok(());
+                }
+            }
+
+            round.outliers = self.slash_outliers(&symbol, round_id, &round.submissions, price)?;
+            self.finalize_round(symbol, round_id, round, price, Self::env().block_timestamp());
+
+            Ok(())
+        }
+
+        /// Escribir el precio finalizado de un round en `prices` y el historial
+        ///
+        /// `timestamp` es el momento al que corresponde el precio.
+        fn finalize_round(
+            &mut self,
+            symbol: String,
+            round_id: u32,
+            round: &mut PriceRound,
+            price: u128,
+            timestamp: u64,
+        ) {
+            let price_data = PriceData {
+                price,
+                timestamp,
+                source_chain: round.source_chain,
+                source_contract: round.source_contract.clone(),
+                validator_count: round.submissions.len() as u32,
+                verified: true,
+                round_id,
+                decimals: self.feeds.get(symbol.clone()).map(|feed| feed.decimals).unwrap_or(0),
+            };
+
+            round.finalized = true;
+            self.prices.insert(symbol.clone(), &price_data);
+            self.latest_round.insert(symbol.clone(), &round_id);
+            self.round_history.insert((symbol.clone(), round_id % MAX_ROUND_HISTORY), &price_data);
+            if round_id > MAX_ROUND_HISTORY {
+                self.rounds.remove((symbol.clone(), round_id - MAX_ROUND_HISTORY));
+            }
+
+            Self::env().emit_event(PriceUpdated {
+                symbol,
+                price,
+                source_chain: price_data.source_chain,
+                verified: true,
+                round_id,
+            });
+        }
+
+        /// Repartir un fee entre los validadores que enviaron el round
+        ///
+        /// Los outliers del round no cobran. El resto de la división rota
+        /// según el round; si todos fueron outliers el fee queda retenido
+        /// junto a los fondos slasheados.
+        fn distribute_fee(&mut self, symbol: &String, round_id: u32, fee: Balance) -> Result<(), Error> {
+            let round = self.rounds.get((symbol.clone(), round_id)).ok_or(Error::InvalidRound)?;
+            if round.submissions.is_empty() {
+                return Err(Error::InvalidRound);
+            }
+
+            let contributors: Vec<AccountId> = round.submissions.iter()
+                .map(|(validator, _)| *validator)
+                .filter(|validator| !round.outliers.contains(validator))
+                .collect();
+            if contributors.is_empty() {
+                self.slashed_funds = self.slashed_funds
+                    .checked_add(fee)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            } else {
+                let count = contributors.len() as u128;
+                let share = fee / count;
+                let remainder = fee % count;
+                let lucky = round_id as usize % contributors.len();
+                for (index, validator) in contributors.iter().enumerate() {
+                    let amount = if index == lucky { share + remainder } else { share };
+                    let rewards = self.validator_rewards.get(validator).unwrap_or(0)
+                        .checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    self.validator_rewards.insert(validator, &rewards);
+                }
+            }
+
+            self.total_fees = self.total_fees
+                .checked_add(fee)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            Ok(())
+        }
+
+        /// Slashear a los validadores cuyo envío se aleja de la mediana más del umbral
+        ///
+        /// Devuelve esos validadores, aunque su bond no dé para recortar.
+        ///
+        /// El recorte se calcula sobre bond más unbonding y sale primero de lo
+        /// que está en unbonding, así un `unbond` antes de finalizar no lo evita.
+        fn slash_outliers(
+            &mut self,
+            symbol: &String,
+            round_id: u32,
+            submissions: &[(AccountId, u128)],
+            median: u128,
+        ) -> Result<Vec<AccountId>, Error> {
+            let mut outliers = Vec::new();
+            let config = self.bond_config;
+            if config.slash_threshold_bps == 0
+                || config.slash_bps == 0
+                || submissions.len() < MIN_SLASH_SUBMISSIONS
+            {
+                return Ok(outliers);
+            }
+
+            let allowed = median
+                .checked_mul(u128::from(config.slash_threshold_bps))
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            for (validator, price) in submissions {
+                let distance = price.abs_diff(median)
+                    .checked_mul(10_000)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                if distance <= allowed {
+                    continue;
+                }
+                outliers.push(*validator);
+
+                let mut bond = self.bonds.get(validator).unwrap_or_default();
+                let amount = bond.bonded
+                    .checked_add(bond.unbonding)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    .checked_mul(Balance::from(config.slash_bps))
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / 10_000;
+                if amount == 0 {
+                    continue;
+                }
+
+                let from_unbonding = amount.min(bond.unbonding);
+                bond.unbonding -= from_unbonding;
+                bond.bonded -= amount - from_unbonding;
+                bond.slashed = bond.slashed
+                    .checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.bonds.insert(validator, &bond);
+                self.slashed_funds = self.slashed_funds
+                    .checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                Self::env().emit_event(ValidatorSlashed {
+                    validator: *validator,
+                    symbol: symbol.clone(),
+                    round_id,
+                    price: *price,
+                    median,
+                    amount,
+                });
+            }
+
+            Ok(outliers)
+        }
+
+        /// Comprobar si `price` está dentro de la desviación máxima respecto a `previous`
+        fn within_deviation(&self, symbol: &String, previous: u128, price: u128) -> Result<bool, Error> {
+            let max_deviation_bps = self.price_bounds.get(symbol)
+                .map(|bounds| bounds.max_deviation_bps)
+                .unwrap_or(0);
+            if max_deviation_bps == 0 {
+                return Ok(true);
+            }
+
+            let change = previous.abs_diff(price)
+                .checked_mul(10_000)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let allowed = previous
+                .checked_mul(u128::from(max_deviation_bps))
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(change <= allowed)
+        }
+
+        /// Comprobar si `submissions` alcanza la supermayoría de validadores
+        fn is_supermajority(&self, submissions: usize) -> Result<bool, Error> {
+            let support = (submissions as u128)
+                .checked_mul(10_000)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let required = (self.validators.len() as u128)
+                .checked_mul(SUPERMAJORITY_BPS)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(support >= required)
+        }
+
+        /// Mediana (promedio de los dos centrales si la cantidad es par)
+        fn median(prices: &mut [u128]) -> Option<u128> {
+            if prices.is_empty() {
+                return None;
+            }
+
+            prices.sort_unstable();
+            let mid = prices.len() / 2;
+            if prices.len() % 2 == 1 {
+                return Some(prices[mid]);
+            }
+
+            let (low, high) = (prices[mid - 1], prices[mid]);
+            Some(low / 2 + high / 2 + (low % 2 + high % 2) / 2)
+        }
+
+        /// Guardar un state root acordado y podar la altura más antigua si sobra
+        fn finalize_state_root(&mut self, chain: ChainId, height: u64, state_root: StateRoot, mut heights: Vec<u64>) {
+            self.state_roots.insert((chain, height), &state_root);
+
+            let position = heights.partition_point(|finalized| *finalized < height);
+            heights.insert(position, height);
+
+            Self::env().emit_event(StateRootFinalized {
+                chain,
+                height,
+                root: state_root.root,
+                timestamp: state_root.timestamp,
+            });
+
+            if heights.len() > MAX_STATE_ROOTS as usize {
+                let pruned = heights.remove(0);
+                self.state_roots.remove((chain, pruned));
+
+                Self::env().emit_event(StateRootPruned {
+                    chain,
+                    height: pruned,
+                });
+            }
+
+            self.state_root_heights.insert(chain, &heights);
+        }
+
+        /// Verificar un state proof contra el root almacenado y decodificar su valor
+        ///
+        /// Devuelve el valor y el timestamp del header del root.
+        /// Primero prueba la cuenta de `contract` en el trie de estado para
+        /// obtener su `storageRoot` y luego la clave contra ese root, así un
+        /// slot de otro contrato no pasa. Una clave ausente también es inválida.
+        fn verify_state_proof(
+            &self,
+            chain: ChainId,
+            contract: [u8; 20],
+            proof: &StateProof,
+        ) -> Result<(u128, u64), Error> {
+            let state_root = self
+                .state_roots
+                .get((chain, proof.height))
+                .ok_or(Error::UnknownStateRoot)?;
+            let account = crate::trie::verify_proof(&state_root.root, &contract, &proof.account_proof)?
+                .ok_or(Error::InvalidProof)?;
+            let storage_root = crate::trie::decode_storage_root(&account)?;
+            let value = crate::trie::verify_proof(&storage_root, &proof.key, &proof.storage_proof)?
+                .ok_or(Error::InvalidProof)?;
+            let decoded = crate::trie::decode_u128(&value)?;
+
+            Self::env().emit_event(StateProofVerified {
+                chain,
+                height: proof.height,
+                contract,
+                key: proof.key.clone(),
+            });
+
+            Ok((decoded, state_root.timestamp))
+        }
+
+        /// Decodificar una dirección EVM de 20 bytes en hex (con o sin `0x`)
+        fn parse_address(contract: &str) -> Result<[u8; 20], Error> {
+            let digits = contract.strip_prefix("0x").unwrap_or(contract).as_bytes();
+            if digits.len() != 40 {
+                return Err(Error::InvalidAddress);
+            }
+
+            let nibble = |digit: u8| char::from(digit).to_digit(16).ok_or(Error::InvalidAddress);
+            let mut address = [0u8; 20];
+            for (byte, pair) in address.iter_mut().zip(digits.chunks(2)) {
+                *byte = (nibble(pair[0])? << 4 | nibble(pair[1])?) as u8;
+            }
+            Ok(address)
+        }
+
+        /// Comparar contratos de origen decodificados, o como texto si alguno
+        /// no es una dirección
+        fn same_contract(a: &str, b: &str) -> bool {
+            match (Self::parse_address(a), Self::parse_address(b)) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => a == b,
+            }
+        }
+
+        /// Verificar que la chain está registrada
+        fn ensure_chain(&self, chain: ChainId) -> Result<(), Error> {
+            if !self.chains.contains(chain) {
+                return Err(Error::InvalidChain);
+            }
+            Ok(())
+        }
+
+        /// Verificar que el caller es el owner
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if Self::env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    impl super::PriceOracle for HyperbridgeOracle {
+        #[ink(message)]
+        fn latest_price(&self, symbol: String) -> Result<PriceData, Error> {
+            self.get_fresh_price(symbol)
+        }
+
+        #[ink(message)]
+        fn price_at_round(&self, symbol: String, round_id: u32) -> Result<PriceData, Error> {
+            self.get_round(symbol, round_id).ok_or(Error::InvalidRound)
+        }
+
+        #[ink(message)]
+        fn decimals(&self, symbol: String) -> Result<u8, Error> {
+            self.feeds.get(symbol)
+                .map(|feed| feed.decimals)
+                .ok_or(Error::FeedNotFound)
+        }
+    }
+
+    /// Errores del contrato
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        ValidatorExists,
+        NotValidator,
+        InvalidPrice,
+        InvalidChain,
+        NotOwner,
+        InvalidThreshold,
+        InvalidRound,
+        DuplicateSubmission,
+        SourceMismatch,
+        PriceNotFound,
+        StalePrice,
+        ArithmeticOverflow,
+        FeedNotFound,
+        InvalidFeed,
+        InvalidWindow,
+        InsufficientHistory,
+        InvalidAmount,
+        InsufficientBond,
+        UnbondingActive,
+        NothingToWithdraw,
+        TransferFailed,
+        InsufficientFee,
+        InvalidProof,
+        UnknownStateRoot,
+        StateRootFinalized,
+        StaleStateRoot,
+        InvalidAddress,
+        StorageSlotNotSet,
+        StaleProof,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+        use ink::env::DefaultEnvironment;
+
+        fn accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        const ETHEREUM: ChainId = ChainId::Evm(1);
+        const ASSET_HUB: ChainId = ChainId::Substrate(1000);
+
+        fn oracle() -> HyperbridgeOracle {
+            set_caller(accounts().alice);
+            HyperbridgeOracle::new(1)
+        }
+
+        #[ink::test]
+        fn validator_management_is_owner_only() {
+            let mut oracle = oracle();
+            assert_eq!(oracle.get_owner(), accounts().alice);
+
+            set_caller(accounts().bob);
+            assert_eq!(oracle.add_validator(accounts().bob), Err(Error::NotOwner));
+            assert_eq!(oracle.set_required_validators(2), Err(Error::NotOwner));
+            assert_eq!(
+                oracle.set_hyperbridge_address(String::from("0x01")),
+                Err(Error::NotOwner)
+            );
+
+            set_caller(accounts().alice);
+            oracle.add_validator(accounts().bob).unwrap();
+            assert_eq!(oracle.add_validator(accounts().bob), Err(Error::ValidatorExists));
+
+            set_caller(accounts().bob);
+            assert_eq!(oracle.remove_validator(accounts().bob), Err(Error::NotOwner));
+
+            set_caller(accounts().alice);
+            oracle.add_validator(accounts().charlie).unwrap();
+            oracle.remove_validator(accounts().bob).unwrap();
+            assert_eq!(oracle.get_validators(), vec![accounts().charlie]);
+            assert_eq!(oracle.remove_validator(accounts().bob), Err(Error::NotValidator));
+        }
+
+        #[ink::test]
+        fn set_required_validators_works() {
+            let mut oracle = oracle_with_validators(1);
+            assert_eq!(oracle.set_required_validators(0), Err(Error::InvalidThreshold));
+            assert_eq!(oracle.set_required_validators(4), Err(Error::InvalidThreshold));
+            oracle.set_required_validators(3).unwrap();
+            assert_eq!(oracle.get_required_validators(), 3);
+        }
+
+        #[ink::test]
+        fn remove_validator_keeps_threshold_reachable() {
+            let mut oracle = oracle_with_validators(3);
+            assert_eq!(oracle.remove_validator(accounts().bob), Err(Error::InvalidThreshold));
+            assert_eq!(oracle.get_validators().len(), 3);
+
+            oracle.set_required_validators(2).unwrap();
+            oracle.remove_validator(accounts().bob).unwrap();
+            assert_eq!(oracle.remove_validator(accounts().charlie), Err(Error::InvalidThreshold));
+        }
+
+        #[ink::test]
+        fn ownership_transfer_is_two_step() {
+            let mut oracle = oracle();
+            oracle.transfer_ownership(accounts().bob).unwrap();
+            assert_eq!(oracle.get_owner(), accounts().alice);
+            assert_eq!(oracle.get_pending_owner(), Some(accounts().bob));
+
+            set_caller(accounts().charlie);
+            assert_eq!(oracle.accept_ownership(), Err(Error::NotOwner));
+
+            set_caller(accounts().bob);
+            oracle.accept_ownership().unwrap();
+            assert_eq!(oracle.get_owner(), accounts().bob);
+            assert_eq!(oracle.get_pending_owner(), None);
+
+            set_caller(accounts().alice);
+            assert_eq!(oracle.add_validator(accounts().alice), Err(Error::NotOwner));
+        }
+
+        /// Oracle con bob, charlie y django como validadores
+        fn oracle_with_validators(required: u32) -> HyperbridgeOracle {
+            let mut oracle = oracle();
+            for validator in [accounts().bob, accounts().charlie, accounts().django] {
+                oracle.add_validator(validator).unwrap();
+            }
+            oracle.set_required_validators(required).unwrap();
+            oracle.register_chain(ETHEREUM, String::from("Ethereum")).unwrap();
+            oracle.register_chain(ASSET_HUB, String::from("Polkadot Asset Hub")).unwrap();
+            oracle.set_feed(
+                String::from("DOT/USD"),
+                String::from("DOT"),
+                String::from("USD"),
+                8,
+                vec![ETHEREUM, ASSET_HUB],
+            ).unwrap();
+            oracle
+        }
+
+        fn submit(oracle: &mut HyperbridgeOracle, validator: AccountId, round_id: u32, price: u128) -> Result<(), Error> {
+            set_caller(validator);
+            oracle.update_price(
+                String::from("DOT/USD"),
+                round_id,
+                price,
+                ETHEREUM,
+                String::from("0xfeed"),
+            )
+        }
+
+        #[ink::test]
+        fn round_finalizes_with_median() {
+            let mut oracle = oracle_with_validators(3);
+            let symbol = String::from("DOT/USD");
+            assert_eq!(oracle.get_current_round(symbol.clone()), 1);
+
+            submit(&mut oracle, accounts().bob, 1, 700).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 100).unwrap();
+            assert!(oracle.get_price(symbol.clone()).is_none());
+            assert_eq!(oracle.verify_price(symbol.clone(), 1), Ok(false));
+
+            submit(&mut oracle, accounts().django, 1, 650).unwrap();
+            let price = oracle.get_price(symbol.clone()).unwrap();
+            assert_eq!(price.price, 650);
+            assert_eq!(price.validator_count, 3);
+            assert_eq!(price.round_id, 1);
+            assert!(price.verified);
+            assert_eq!(oracle.verify_price(symbol.clone(), 1), Ok(true));
+            assert_eq!(oracle.get_current_round(symbol), 2);
+        }
+
+        #[ink::test]
+        fn even_round_averages_middle_prices() {
+            let mut oracle = oracle_with_validators(2);
+            submit(&mut oracle, accounts().bob, 1, 101).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 200).unwrap();
+            assert_eq!(oracle.get_price(String::from("DOT/USD")).unwrap().price, 150);
+        }
+
+        #[ink::test]
+        fn round_submission_errors() {
+            let mut oracle = oracle_with_validators(2);
+            assert_eq!(submit(&mut oracle, accounts().eve, 1, 100), Err(Error::NotValidator));
+            assert_eq!(submit(&mut oracle, accounts().bob, 2, 100), Err(Error::InvalidRound));
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            assert_eq!(submit(&mut oracle, accounts().bob, 1, 100), Err(Error::DuplicateSubmission));
+
+            set_caller(accounts().charlie);
+            assert_eq!(
+                oracle.update_price(
+                    String::from("DOT/USD"),
+                    1,
+                    100,
+                    ASSET_HUB,
+                    String::from("0xfeed"),
+                ),
+                Err(Error::SourceMismatch)
+            );
+
+            submit(&mut oracle, accounts().charlie, 1, 100).unwrap();
+            assert_eq!(submit(&mut oracle, accounts().django, 1, 100), Err(Error::InvalidRound));
+            assert_eq!(oracle.verify_price(String::from("DOT/USD"), 3), Err(Error::InvalidRound));
+        }
+
+        #[ink::test]
+        fn stalled_round_reopens_after_timeout() {
+            let mut oracle = oracle_with_validators(2);
+            let symbol = String::from("DOT/USD");
+            let submit_from = |oracle: &mut HyperbridgeOracle, validator, chain| {
+                set_caller(validator);
+                oracle.update_price(symbol.clone(), 1, 100, chain, String::from("0xfeed"))
+            };
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            submit_from(&mut oracle, accounts().bob, ETHEREUM).unwrap();
+            assert_eq!(submit_from(&mut oracle, accounts().charlie, ASSET_HUB), Err(Error::SourceMismatch));
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_000 + ROUND_TIMEOUT - 1);
+            assert_eq!(submit_from(&mut oracle, accounts().charlie, ASSET_HUB), Err(Error::SourceMismatch));
+
+            // Vencido el timeout, el envío de charlie reabre el round con su fuente
+            test::set_block_timestamp::<DefaultEnvironment>(1_000 + ROUND_TIMEOUT);
+            submit_from(&mut oracle, accounts().charlie, ASSET_HUB).unwrap();
+            assert_eq!(
+                oracle.get_round_submissions(symbol.clone(), 1),
+                vec![(accounts().charlie, 100)]
+            );
+
+            submit_from(&mut oracle, accounts().bob, ASSET_HUB).unwrap();
+            let price = oracle.get_price(symbol).unwrap();
+            assert_eq!(price.source_chain, ASSET_HUB);
+            assert_eq!(price.validator_count, 2);
+        }
+
+        #[ink::test]
+        fn fresh_price_respects_heartbeat() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            assert_eq!(oracle.get_fresh_price(symbol.clone()).err(), Some(Error::PriceNotFound));
+
+            set_caller(accounts().alice);
+            oracle.set_price_bounds(symbol.clone(), 1_000, 0).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+
+            test::set_block_timestamp::<DefaultEnvironment>(6_000);
+            assert_eq!(oracle.get_fresh_price(symbol.clone()).unwrap().price, 100);
+
+            test::set_block_timestamp::<DefaultEnvironment>(6_001);
+            assert_eq!(oracle.get_fresh_price(symbol.clone()).err(), Some(Error::StalePrice));
+            assert!(oracle.get_price(symbol).is_some());
+        }
+
+        #[ink::test]
+        fn deviation_requires_supermajority() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+
+            set_caller(accounts().alice);
+            oracle.set_price_bounds(symbol.clone(), 0, 1_000).unwrap();
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+
+            // +10% entra en el límite
+            submit(&mut oracle, accounts().bob, 2, 110).unwrap();
+            assert_eq!(oracle.get_price(symbol.clone()).unwrap().price, 110);
+
+            // +50% necesita 2 de 3 validadores
+            submit(&mut oracle, accounts().bob, 3, 165).unwrap();
+            assert_eq!(oracle.verify_price(symbol.clone(), 3), Ok(false));
+            assert_eq!(oracle.get_price(symbol.clone()).unwrap().price, 110);
+
+            submit(&mut oracle, accounts().charlie, 3, 165).unwrap();
+            assert_eq!(oracle.verify_price(symbol.clone(), 3), Ok(true));
+            assert_eq!(oracle.get_price(symbol).unwrap().price, 165);
+        }
+
+        #[ink::test]
+        fn owner_can_force_finalize_stuck_round() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+
+            set_caller(accounts().alice);
+            oracle.set_price_bounds(symbol.clone(), 0, 1_000).unwrap();
+            assert_eq!(oracle.force_finalize_round(symbol.clone(), 1), Err(Error::InvalidRound));
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().bob, 2, 165).unwrap();
+            assert_eq!(oracle.verify_price(symbol.clone(), 2), Ok(false));
+
+            set_caller(accounts().charlie);
+            assert_eq!(oracle.force_finalize_round(symbol.clone(), 2), Err(Error::NotOwner));
+
+            set_caller(accounts().alice);
+            assert_eq!(oracle.force_finalize_round(symbol.clone(), 1), Err(Error::InvalidRound));
+            oracle.force_finalize_round(symbol.clone(), 2).unwrap();
+            assert_eq!(oracle.verify_price(symbol.clone(), 2), Ok(true));
+            assert_eq!(oracle.get_price(symbol.clone()).unwrap().price, 165);
+            assert_eq!(oracle.force_finalize_round(symbol, 2), Err(Error::InvalidRound));
+        }
+
+        #[ink::test]
+        fn invalid_prices_and_bounds_fail() {
+            let mut oracle = oracle_with_validators(1);
+            assert_eq!(submit(&mut oracle, accounts().bob, 1, 0), Err(Error::InvalidPrice));
+
+            set_caller(accounts().alice);
+            assert_eq!(
+                oracle.set_price_bounds(String::from("DOT/USD"), 0, 10_001),
+                Err(Error::InvalidThreshold)
+            );
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.set_price_bounds(String::from("DOT/USD"), 0, 100),
+                Err(Error::NotOwner)
+            );
+        }
+
+        #[ink::test]
+        fn feed_registry_works() {
+            let mut oracle = oracle_with_validators(1);
+            let feeds = oracle.list_feeds();
+            assert_eq!(feeds.len(), 1);
+            assert_eq!(feeds[0].0, String::from("DOT/USD"));
+            assert_eq!(feeds[0].1.quote, String::from("USD"));
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            assert_eq!(oracle.get_price(String::from("DOT/USD")).unwrap().decimals, 8);
+
+            set_caller(accounts().alice);
+            oracle.remove_feed(String::from("DOT/USD")).unwrap();
+            assert!(oracle.list_feeds().is_empty());
+            assert_eq!(oracle.remove_feed(String::from("DOT/USD")), Err(Error::FeedNotFound));
+            assert_eq!(submit(&mut oracle, accounts().bob, 2, 100), Err(Error::FeedNotFound));
+        }
+
+        #[ink::test]
+        fn update_price_validates_feed() {
+            let mut oracle = oracle_with_validators(1);
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.update_price(
+                    String::from("DOT/USDT"),
+                    1,
+                    100,
+                    ETHEREUM,
+                    String::from("0xfeed"),
+                ),
+                Err(Error::FeedNotFound)
+            );
+            assert_eq!(
+                oracle.update_price(
+                    String::from("DOT/USD"),
+                    1,
+                    100,
+                    ChainId::Evm(137),
+                    String::from("0xfeed"),
+                ),
+                Err(Error::InvalidChain)
+            );
+
+            set_caller(accounts().alice);
+            assert_eq!(
+                oracle.set_feed(String::from("ETH/USD"), String::from("ETH"), String::from("USD"), 8, Vec::new()),
+                Err(Error::InvalidFeed)
+            );
+            assert_eq!(
+                oracle.set_price_bounds(String::from("ETH/USD"), 0, 100),
+                Err(Error::FeedNotFound)
+            );
+        }
+
+        fn submit_at(oracle: &mut HyperbridgeOracle, timestamp: u64, round_id: u32, price: u128) {
+            test::set_block_timestamp::<DefaultEnvironment>(timestamp);
+            submit(oracle, accounts().bob, round_id, price).unwrap();
+        }
+
+        #[ink::test]
+        fn rounds_are_kept_in_ring_buffer() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            assert_eq!(oracle.get_latest_round_id(symbol.clone()), None);
+
+            for round_id in 1..=MAX_ROUND_HISTORY + 2 {
+                submit_at(&mut oracle, u64::from(round_id) * 10, round_id, u128::from(round_id));
+            }
+
+            assert_eq!(oracle.get_latest_round_id(symbol.clone()), Some(MAX_ROUND_HISTORY + 2));
+            assert_eq!(oracle.get_round(symbol.clone(), 1).map(|p| p.price), None);
+            assert_eq!(oracle.get_round(symbol.clone(), 2).map(|p| p.price), None);
+            assert_eq!(oracle.get_round(symbol.clone(), 3).map(|p| p.price), Some(3));
+            assert_eq!(oracle.verify_price(symbol.clone(), 2), Err(Error::InvalidRound));
+            assert_eq!(oracle.verify_price(symbol, 3), Ok(true));
+        }
+
+        #[ink::test]
+        fn twap_weights_prices_by_time() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            assert_eq!(oracle.get_twap(symbol.clone(), 100), Err(Error::PriceNotFound));
+
+            submit_at(&mut oracle, 1_000, 1, 100);
+            submit_at(&mut oracle, 1_300, 2, 200);
+            submit_at(&mut oracle, 1_400, 3, 400);
+
+            test::set_block_timestamp::<DefaultEnvironment>(1_500);
+            // 100 * 100 + 200 * 100 + 400 * 100 sobre 300 ms
+            assert_eq!(oracle.get_twap(symbol.clone(), 300), Ok(233));
+            // 100 * 300 + 200 * 100 + 400 * 100 sobre 500 ms
+            assert_eq!(oracle.get_twap(symbol.clone(), 500), Ok(180));
+            assert_eq!(oracle.get_twap(symbol.clone(), 50), Ok(400));
+            assert_eq!(oracle.get_twap(symbol.clone(), 501), Err(Error::InsufficientHistory));
+            assert_eq!(oracle.get_twap(symbol, 0), Err(Error::InvalidWindow));
+        }
+
+        #[ink::test]
+        fn price_oracle_trait_works() {
+            use crate::PriceOracle;
+
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            assert_eq!(oracle.latest_price(symbol.clone()).err(), Some(Error::PriceNotFound));
+            assert_eq!(oracle.decimals(symbol.clone()), Ok(8));
+            assert_eq!(oracle.decimals(String::from("ETH/USD")), Err(Error::FeedNotFound));
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            assert_eq!(oracle.latest_price(symbol.clone()).unwrap().price, 100);
+            assert_eq!(oracle.price_at_round(symbol.clone(), 1).unwrap().round_id, 1);
+            assert_eq!(oracle.price_at_round(symbol, 2).err(), Some(Error::InvalidRound));
+        }
+
+        fn bond(oracle: &mut HyperbridgeOracle, validator: AccountId, amount: Balance) -> Result<(), Error> {
+            set_caller(validator);
+            test::set_value_transferred::<DefaultEnvironment>(amount);
+            let result = oracle.bond();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            result
+        }
+
+        fn bond_config() -> BondConfig {
+            BondConfig {
+                min_bond: 1_000,
+                slash_threshold_bps: 1_000,
+                slash_bps: 5_000,
+                unbonding_period: 100,
+            }
+        }
+
+        #[ink::test]
+        fn submissions_require_min_bond() {
+            let mut oracle = oracle_with_validators(1);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+
+            assert_eq!(submit(&mut oracle, accounts().bob, 1, 100), Err(Error::InsufficientBond));
+            assert_eq!(bond(&mut oracle, accounts().bob, 0), Err(Error::InvalidAmount));
+            bond(&mut oracle, accounts().bob, 999).unwrap();
+            assert!(!oracle.get_validator_info(accounts().bob).meets_min_bond);
+            bond(&mut oracle, accounts().bob, 1).unwrap();
+
+            let info = oracle.get_validator_info(accounts().bob);
+            assert!(info.is_validator);
+            assert!(info.meets_min_bond);
+            assert_eq!(info.bond.bonded, 1_000);
+            assert_eq!(submit(&mut oracle, accounts().bob, 1, 100), Ok(()));
+        }
+
+        #[ink::test]
+        fn outliers_are_slashed_at_finalization() {
+            let mut oracle = oracle_with_validators(3);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+            for validator in [accounts().bob, accounts().charlie, accounts().django] {
+                bond(&mut oracle, validator, 1_000).unwrap();
+            }
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 105).unwrap();
+            submit(&mut oracle, accounts().django, 1, 150).unwrap();
+
+            // Mediana 105: django se aleja ~43%, bob ~5%
+            assert_eq!(oracle.get_validator_info(accounts().bob).bond.bonded, 1_000);
+            assert_eq!(oracle.get_validator_info(accounts().charlie).bond.bonded, 1_000);
+            let django = oracle.get_validator_info(accounts().django);
+            assert_eq!(django.bond.bonded, 500);
+            assert_eq!(django.bond.slashed, 500);
+            assert!(!django.meets_min_bond);
+            assert_eq!(oracle.get_slashed_funds(), 500);
+            assert_eq!(submit(&mut oracle, accounts().django, 2, 105), Err(Error::InsufficientBond));
+        }
+
+        #[ink::test]
+        fn unbonding_does_not_escape_slash() {
+            let mut oracle = oracle_with_validators(3);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+            for validator in [accounts().bob, accounts().charlie, accounts().django] {
+                bond(&mut oracle, validator, 1_000).unwrap();
+            }
+
+            submit(&mut oracle, accounts().django, 1, 150).unwrap();
+            set_caller(accounts().django);
+            oracle.unbond(1_000).unwrap();
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 105).unwrap();
+
+            let django = oracle.get_validator_info(accounts().django).bond;
+            assert_eq!(django.bonded, 0);
+            assert_eq!(django.unbonding, 500);
+            assert_eq!(django.slashed, 500);
+            assert_eq!(oracle.get_slashed_funds(), 500);
+        }
+
+        #[ink::test]
+        fn two_submissions_are_not_slashed() {
+            let mut oracle = oracle_with_validators(2);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+            for validator in [accounts().bob, accounts().charlie] {
+                bond(&mut oracle, validator, 1_000).unwrap();
+            }
+
+            // Mediana 125: ambos se alejan un 20%, no se puede saber quién miente
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 150).unwrap();
+            assert_eq!(oracle.get_price(String::from("DOT/USD")).unwrap().price, 125);
+
+            for validator in [accounts().bob, accounts().charlie] {
+                assert_eq!(oracle.get_validator_info(validator).bond.slashed, 0);
+            }
+            assert_eq!(oracle.get_slashed_funds(), 0);
+        }
+
+        #[ink::test]
+        fn unbonding_waits_for_delay() {
+            let mut oracle = oracle_with_validators(1);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+            bond(&mut oracle, accounts().bob, 1_000).unwrap();
+
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            test::set_account_balance::<DefaultEnvironment>(contract, 1_000);
+
+            test::set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller(accounts().bob);
+            assert_eq!(oracle.withdraw_unbonded(), Err(Error::NothingToWithdraw));
+            assert_eq!(oracle.unbond(1_001), Err(Error::InsufficientBond));
+            oracle.unbond(400).unwrap();
+            assert_eq!(oracle.get_validator_info(accounts().bob).bond.unlock_at, 110);
+
+            test::set_block_timestamp::<DefaultEnvironment>(109);
+            assert_eq!(oracle.withdraw_unbonded(), Err(Error::UnbondingActive));
+
+            test::set_block_timestamp::<DefaultEnvironment>(110);
+            let before = test::get_account_balance::<DefaultEnvironment>(accounts().bob).unwrap();
+            assert_eq!(oracle.withdraw_unbonded(), Ok(400));
+            let after = test::get_account_balance::<DefaultEnvironment>(accounts().bob).unwrap();
+            assert_eq!(after - before, 400);
+
+            let info = oracle.get_validator_info(accounts().bob);
+            assert_eq!(info.bond.bonded, 600);
+            assert_eq!(info.bond.unbonding, 0);
+        }
+
+        #[ink::test]
+        fn paid_queries_reward_round_validators() {
+            let mut oracle = oracle_with_validators(2);
+            let symbol = String::from("DOT/USD");
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 110).unwrap();
+
+            set_caller(accounts().alice);
+            oracle.set_query_fee(11).unwrap();
+
+            set_caller(accounts().eve);
+            assert_eq!(oracle.query_price(symbol.clone()).err(), Some(Error::InsufficientFee));
+
+            // Pago directo con excedente a la suscripción
+            test::set_value_transferred::<DefaultEnvironment>(15);
+            assert_eq!(oracle.query_price(symbol.clone()).unwrap().price, 105);
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            assert_eq!(oracle.get_subscription_balance(accounts().eve), 4);
+
+            test::set_value_transferred::<DefaultEnvironment>(20);
+            assert_eq!(oracle.deposit_subscription(), Ok(24));
+            test::set_value_transferred::<DefaultEnvironment>(0);
+            oracle.query_price(symbol).unwrap();
+            assert_eq!(oracle.get_subscription_balance(accounts().eve), 13);
+
+            // El resto de cada fee va a charlie (round 1 % 2 envíos)
+            assert_eq!(oracle.get_total_fees(), 22);
+            assert_eq!(oracle.get_validator_rewards(accounts().bob), 10);
+            assert_eq!(oracle.get_validator_rewards(accounts().charlie), 12);
+            assert_eq!(oracle.get_validator_rewards(accounts().django), 0);
+        }
+
+        #[ink::test]
+        fn outliers_get_no_fee_share() {
+            let mut oracle = oracle_with_validators(3);
+            set_caller(accounts().alice);
+            oracle.set_bond_config(bond_config()).unwrap();
+            oracle.set_query_fee(11).unwrap();
+            for validator in [accounts().bob, accounts().charlie, accounts().django] {
+                bond(&mut oracle, validator, 1_000).unwrap();
+            }
+
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+            submit(&mut oracle, accounts().charlie, 1, 105).unwrap();
+            submit(&mut oracle, accounts().django, 1, 150).unwrap();
+
+            set_caller(accounts().eve);
+            test::set_value_transferred::<DefaultEnvironment>(11);
+            oracle.query_price(String::from("DOT/USD")).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+
+            assert_eq!(oracle.get_validator_rewards(accounts().bob), 5);
+            assert_eq!(oracle.get_validator_rewards(accounts().charlie), 6);
+            assert_eq!(oracle.get_validator_rewards(accounts().django), 0);
+            assert_eq!(oracle.get_slashed_funds(), 500);
+        }
+
+        #[ink::test]
+        fn claim_validator_rewards_works() {
+            let mut oracle = oracle_with_validators(1);
+            submit(&mut oracle, accounts().bob, 1, 100).unwrap();
+
+            set_caller(accounts().alice);
+            oracle.set_query_fee(50).unwrap();
+
+            set_caller(accounts().eve);
+            test::set_value_transferred::<DefaultEnvironment>(50);
+            oracle.query_price(String::from("DOT/USD")).unwrap();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            test::set_account_balance::<DefaultEnvironment>(contract, 50);
+
+            set_caller(accounts().bob);
+            assert_eq!(oracle.claim_validator_rewards(), Ok(50));
+            assert_eq!(oracle.claim_validator_rewards(), Err(Error::NothingToWithdraw));
+            assert_eq!(oracle.get_validator_rewards(accounts().bob), 0);
+        }
+
+        /// State root de fixture: trie de cuentas con 0x…feed, 0x…beef y tres cuentas más
+        const FIXTURE_ROOT: &str = "4d2f5937cda2f397dc19ec9a5e0163cc4c7a148bb793511b47b10c97bdc59c4e";
+        /// Contrato con el precio (slot 0 = 750_000_000) y el mapping de balances (slot 2)
+        const FEED_CONTRACT: &str = "0x000000000000000000000000000000000000feed";
+        /// Otro contrato del mismo state root (slot 0 = 999_000_000)
+        const BEEF_CONTRACT: &str = "0x000000000000000000000000000000000000beef";
+        const ACCOUNT_PROOF_FEED: &[&str] = &[
+            "f891a05a81d27a75c2421a52b299bca5cd42e0c648088a498ff5e16f227796b5de10c7a07759dbc485a2c62b9ffab3181389238c306d2748d9bcead3c32d8928f4820152808080a000e9b23f5e4ace58f33b20bd362a2d12ae77c3c0a3fe30778878eb2295c123cd80808080808080a04a8d2633acaa24bf17c24cca5284cfc649abb0a7ebd75e24fe4d296433725475808080",
+            "f8518080808080808080808080a009ed89ab6f5f6073b309ed3e04f6679783ec1617be3303e358af0d33eaf3751180a0f28a3da8c25d05b7c9863bc9ce4b7aed0a7d368cac7169c7ff080db6a1a9c1b3808080",
+            "f869a0201de5ee675b26abbb20c329b4aa5c0e374d64cc7794069bca37081062de9429b846f8440180a0fbd0902daa0f06e6bb60b6b3ebde6bd001c4cd977aeaa1cc90ed8f81bb9bfc8ea0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ];
+        const ACCOUNT_PROOF_BEEF: &[&str] = &[
+            "f891a05a81d27a75c2421a52b299bca5cd42e0c648088a498ff5e16f227796b5de10c7a07759dbc485a2c62b9ffab3181389238c306d2748d9bcead3c32d8928f4820152808080a000e9b23f5e4ace58f33b20bd362a2d12ae77c3c0a3fe30778878eb2295c123cd80808080808080a04a8d2633acaa24bf17c24cca5284cfc649abb0a7ebd75e24fe4d296433725475808080",
+            "f869a03bdc87366b295f669b3e2c2dd1bba5d06dbdba4132daeb657842f8c809ce62e0b846f8440180a0baf88ea8feca3f6d79e9b8448c5f68399c5c68d7e4ee24f5f4baa906660cd846a0c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ];
+        /// Slot 0 de 0x…feed
+        const STORAGE_PROOF_PRICE: &[&str] = &[
+            "f8718080a088afa214cd0a5a9fd84ea8a1c2d205e106907f6159951d344b61629efa9af8aba09675d5a637c1cbd74db4a12bc4f2e006c4b99514450c6d79767fd0cf24df0fbf80808080808080a09795f96729b220d2ea217ffc01a4cffda73b15e0f64b4e1a9e452087fc4f47288080808080",
+            "e7a0390decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e56385842cb41780",
+        ];
+        /// Balance de frank (10^24) en el mapping de 0x…feed
+        const STORAGE_PROOF_FRANK: &[&str] = &[
+            "f8718080a088afa214cd0a5a9fd84ea8a1c2d205e106907f6159951d344b61629efa9af8aba09675d5a637c1cbd74db4a12bc4f2e006c4b99514450c6d79767fd0cf24df0fbf80808080808080a09795f96729b220d2ea217ffc01a4cffda73b15e0f64b4e1a9e452087fc4f47288080808080",
+            "f85180a08cf65aebbd211554339725d460f97a3998a18e1c6207791cc8b01dd157b15bce808080808080808080808080a047a2c5f39fe6c8b51e3da059da80277eccd40bfd8421fe787daf9822eaba89bf8080",
+            "eda020c2dcef0ab28ec7f466e8463d76b3549e2e360c7dfd74230342ccfd0e01c9108b8ad3c21bcecceda1000000",
+        ];
+        /// Balance de eve (5 * 10^18) en el mapping de 0x…feed
+        const STORAGE_PROOF_EVE: &[&str] = &[
+            "f8718080a088afa214cd0a5a9fd84ea8a1c2d205e106907f6159951d344b61629efa9af8aba09675d5a637c1cbd74db4a12bc4f2e006c4b99514450c6d79767fd0cf24df0fbf80808080808080a09795f96729b220d2ea217ffc01a4cffda73b15e0f64b4e1a9e452087fc4f47288080808080",
+            "eba038c0155f2500fc5a81fcdb05fb1f4f6aeaa6b5ab7e5b084a21249e5b5edb112b89884563918244f40000",
+        ];
+        /// Slot 9 de 0x…feed, que no existe
+        const STORAGE_PROOF_ABSENT: &[&str] = &[
+            "f8718080a088afa214cd0a5a9fd84ea8a1c2d205e106907f6159951d344b61629efa9af8aba09675d5a637c1cbd74db4a12bc4f2e006c4b99514450c6d79767fd0cf24df0fbf80808080808080a09795f96729b220d2ea217ffc01a4cffda73b15e0f64b4e1a9e452087fc4f47288080808080",
+        ];
+        /// Slot 0 de 0x…beef
+        const STORAGE_PROOF_BEEF_PRICE: &[&str] = &[
+            "f8518080a091fa24699f87453380304b4d0767703ca10dd14f31e0e82d0983c6d6ce9bbc6b8080808080808080a04784fb4f62cc58a00b3ae50ff62c0a3672be2328c21840707284bb09a77ca21a8080808080",
+            "e7a0390decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e56385843b8b87c0",
+        ];
+
+        fn hex(input: &str) -> Vec<u8> {
+            let input = input.strip_prefix("0x").unwrap_or(input);
+            (0..input.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&input[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        fn nodes(proof: &[&str]) -> Vec<Vec<u8>> {
+            proof.iter().map(|node| hex(node)).collect()
+        }
+
+        fn fixture_root() -> [u8; 32] {
+            hex(FIXTURE_ROOT).try_into().unwrap()
+        }
+
+        fn slot(index: u8) -> [u8; 32] {
+            let mut key = [0u8; 32];
+            key[31] = index;
+            key
+        }
+
+        fn balance_key(account: AccountId) -> [u8; 32] {
+            crate::trie::mapping_key(account.as_ref(), &slot(2))
+        }
+
+        fn storage_slot(contract: &str, index: u8) -> StorageSlot {
+            StorageSlot {
+                contract: String::from(contract),
+                slot: slot(index),
+            }
+        }
+
+        fn state_proof(height: u64, key: [u8; 32], account_proof: &[&str], storage_proof: &[&str]) -> StateProof {
+            StateProof {
+                height,
+                key: key.to_vec(),
+                account_proof: nodes(account_proof),
+                storage_proof: nodes(storage_proof),
+            }
+        }
+
+        /// Finalizar el root de fixture para `ETHEREUM` votando hasta que se acepte
+        fn finalize_fixture_root(oracle: &mut HyperbridgeOracle, height: u64) {
+            for validator in [accounts().bob, accounts().charlie, accounts().django] {
+                if oracle.get_state_root(ETHEREUM, height).is_some() {
+                    break;
+                }
+                set_caller(validator);
+                oracle
+                    .submit_state_root(ETHEREUM, height, fixture_root(), 1_000)
+                    .unwrap();
+            }
+        }
+
+        /// Storage root de `contract` según el proof de su cuenta
+        fn storage_root(contract: &str, account_proof: &[&str]) -> [u8; 32] {
+            let account = crate::trie::verify_proof(&fixture_root(), &hex(contract), &nodes(account_proof))
+                .unwrap()
+                .unwrap();
+            crate::trie::decode_storage_root(&account).unwrap()
+        }
+
+        #[ink::test]
+        fn trie_verifies_fixture_proofs() {
+            let root = storage_root(FEED_CONTRACT, ACCOUNT_PROOF_FEED);
+            let price = crate::trie::verify_proof(&root, &slot(0), &nodes(STORAGE_PROOF_PRICE))
+                .unwrap()
+                .unwrap();
+            assert_eq!(crate::trie::decode_u128(&price), Ok(750_000_000));
+
+            let frank = balance_key(accounts().frank);
+            let balance = crate::trie::verify_proof(&root, &frank, &nodes(STORAGE_PROOF_FRANK))
+                .unwrap()
+                .unwrap();
+            assert_eq!(crate::trie::decode_u128(&balance), Ok(1_000_000_000_000_000_000_000_000));
+
+            let beef_root = storage_root(BEEF_CONTRACT, ACCOUNT_PROOF_BEEF);
+            let beef_price = crate::trie::verify_proof(&beef_root, &slot(0), &nodes(STORAGE_PROOF_BEEF_PRICE))
+                .unwrap()
+                .unwrap();
+            assert_eq!(crate::trie::decode_u128(&beef_price), Ok(999_000_000));
+
+            // Slot 9 no existe: el branch prueba su ausencia
+            assert_eq!(crate::trie::verify_proof(&root, &slot(9), &nodes(STORAGE_PROOF_ABSENT)), Ok(None));
+
+            // Hoja de otra clave, hoja alterada, root distinto o valor que no es una cuenta
+            assert_eq!(
+                crate::trie::verify_proof(&root, &frank, &nodes(STORAGE_PROOF_EVE)),
+                Err(Error::InvalidProof)
+            );
+            let mut tampered = nodes(STORAGE_PROOF_PRICE);
+            *tampered[1].last_mut().unwrap() ^= 1;
+            assert_eq!(crate::trie::verify_proof(&root, &slot(0), &tampered), Err(Error::InvalidProof));
+            assert_eq!(
+                crate::trie::verify_proof(&[0u8; 32], &slot(0), &nodes(STORAGE_PROOF_PRICE)),
+                Err(Error::InvalidProof)
+            );
+            assert_eq!(crate::trie::decode_storage_root(&price), Err(Error::InvalidProof));
+        }
+
+        #[ink::test]
+        fn update_price_with_proof_works() {
+            let mut oracle = oracle_with_validators(2);
+            let symbol = String::from("DOT/USD");
+
+            assert_eq!(
+                oracle.set_price_storage_key(String::from("BTC/USD"), storage_slot(FEED_CONTRACT, 0)),
+                Err(Error::FeedNotFound)
+            );
+            assert_eq!(
+                oracle.set_price_storage_key(symbol.clone(), storage_slot("0xfeed", 0)),
+                Err(Error::InvalidAddress)
+            );
+
+            set_caller(accounts().bob);
+            let update = |oracle: &mut HyperbridgeOracle, proof: StateProof| {
+                oracle.update_price_with_proof(symbol.clone(), ETHEREUM, String::from(FEED_CONTRACT), proof)
+            };
+            let price_proof = || state_proof(100, slot(0), ACCOUNT_PROOF_FEED, STORAGE_PROOF_PRICE);
+            assert_eq!(update(&mut oracle, price_proof()), Err(Error::StorageSlotNotSet));
+
+            set_caller(accounts().alice);
+            oracle.set_price_storage_key(symbol.clone(), storage_slot(FEED_CONTRACT, 0)).unwrap();
+
+            // Sin state root registrado
+            set_caller(accounts().bob);
+            assert_eq!(update(&mut oracle, price_proof()), Err(Error::UnknownStateRoot));
+
+            finalize_fixture_root(&mut oracle, 100);
+
+            set_caller(accounts().eve);
+            assert_eq!(update(&mut oracle, price_proof()), Err(Error::NotValidator));
+
+            // Clave distinta de la registrada para el feed
+            set_caller(accounts().bob);
+            assert_eq!(
+                update(
+                    &mut oracle,
+                    state_proof(100, balance_key(accounts().frank), ACCOUNT_PROOF_FEED, STORAGE_PROOF_FRANK),
+                ),
+                Err(Error::InvalidProof)
+            );
+
+            let mut tampered = price_proof();
+            *tampered.storage_proof[1].last_mut().unwrap() ^= 1;
+            assert_eq!(update(&mut oracle, tampered), Err(Error::InvalidProof));
+            assert_eq!(oracle.get_price(symbol.clone()).map(|data| data.price), None);
+
+            // Un solo proof válido finaliza el round aunque se requieran 2 validadores
+            update(&mut oracle, price_proof()).unwrap();
+            let data = oracle.get_price(symbol.clone()).unwrap();
+            assert_eq!(data.price, 750_000_000);
+            assert_eq!(data.round_id, 1);
+            assert_eq!(data.validator_count, 1);
+            assert!(data.verified);
+            assert_eq!(oracle.get_current_round(symbol.clone()), 2);
+
+            // El mismo proof no puede reutilizarse
+            set_caller(accounts().charlie);
+            assert_eq!(update(&mut oracle, price_proof()), Err(Error::StaleProof));
+        }
+
+        #[ink::test]
+        fn proof_from_other_contract_is_rejected() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            oracle.set_price_storage_key(symbol.clone(), storage_slot(FEED_CONTRACT, 0)).unwrap();
+            finalize_fixture_root(&mut oracle, 100);
+
+            set_caller(accounts().bob);
+            let update = |oracle: &mut HyperbridgeOracle, contract: &str, account_proof, storage_proof| {
+                oracle.update_price_with_proof(
+                    symbol.clone(),
+                    ETHEREUM,
+                    String::from(contract),
+                    state_proof(100, slot(0), account_proof, storage_proof),
+                )
+            };
+
+            // Proof válido del slot 0 de 0x…beef, pero el feed lee de 0x…feed
+            assert_eq!(
+                update(&mut oracle, BEEF_CONTRACT, ACCOUNT_PROOF_BEEF, STORAGE_PROOF_BEEF_PRICE),
+                Err(Error::SourceMismatch)
+            );
+            // El slot de 0x…beef no cuadra con el storage root de 0x…feed
+            assert_eq!(
+                update(&mut oracle, FEED_CONTRACT, ACCOUNT_PROOF_FEED, STORAGE_PROOF_BEEF_PRICE),
+                Err(Error::InvalidProof)
+            );
+            // La cuenta de 0x…beef no prueba la de 0x…feed
+            assert_eq!(
+                update(&mut oracle, FEED_CONTRACT, ACCOUNT_PROOF_BEEF, STORAGE_PROOF_BEEF_PRICE),
+                Err(Error::InvalidProof)
+            );
+            assert!(oracle.get_price(symbol.clone()).is_none());
+
+            // La dirección se compara decodificada, no como texto
+            update(&mut oracle, &FEED_CONTRACT[2..], ACCOUNT_PROOF_FEED, STORAGE_PROOF_PRICE).unwrap();
+            assert_eq!(oracle.get_price(symbol).unwrap().price, 750_000_000);
+        }
+
+        #[ink::test]
+        fn median_submissions_are_pinned_to_the_proof_contract() {
+            let mut oracle = oracle_with_validators(2);
+            let symbol = String::from("DOT/USD");
+            oracle.set_price_storage_key(symbol.clone(), storage_slot(FEED_CONTRACT, 0)).unwrap();
+            finalize_fixture_root(&mut oracle, 100);
+
+            // Con contrato registrado, la mediana no acepta otro
+            assert_eq!(submit(&mut oracle, accounts().bob, 1, 100), Err(Error::SourceMismatch));
+            assert_eq!(
+                oracle.update_price(symbol.clone(), 1, 100, ETHEREUM, String::from(BEEF_CONTRACT)),
+                Err(Error::SourceMismatch)
+            );
+
+            // Un round abierto con otra grafía de la misma dirección no bloquea el proof
+            let upper = format!("0x{}", FEED_CONTRACT[2..].to_uppercase());
+            oracle.update_price(symbol.clone(), 1, 100, ETHEREUM, upper).unwrap();
+
+            set_caller(accounts().charlie);
+            oracle.update_price_with_proof(
+                symbol.clone(),
+                ETHEREUM,
+                String::from(FEED_CONTRACT),
+                state_proof(100, slot(0), ACCOUNT_PROOF_FEED, STORAGE_PROOF_PRICE),
+            ).unwrap();
+            assert_eq!(oracle.get_price(symbol).unwrap().price, 750_000_000);
+        }
+
+        #[ink::test]
+        fn update_balance_with_proof_works() {
+            let mut oracle = oracle_with_validators(1);
+            let (frank, eve) = (accounts().frank, accounts().eve);
+            let frank_proof = |height| state_proof(height, balance_key(frank), ACCOUNT_PROOF_FEED, STORAGE_PROOF_FRANK);
+            let eve_proof = || state_proof(7, balance_key(eve), ACCOUNT_PROOF_FEED, STORAGE_PROOF_EVE);
+            finalize_fixture_root(&mut oracle, 7);
+
+            set_caller(accounts().eve);
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(7)),
+                Err(Error::NotValidator)
+            );
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(7)),
+                Err(Error::StorageSlotNotSet)
+            );
+
+            set_caller(accounts().alice);
+            assert_eq!(
+                oracle.set_balance_storage_slot(ChainId::Evm(137), storage_slot(FEED_CONTRACT, 2)),
+                Err(Error::InvalidChain)
+            );
+            oracle.set_balance_storage_slot(ETHEREUM, storage_slot(FEED_CONTRACT, 2)).unwrap();
+            oracle.set_bond_config(bond_config()).unwrap();
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(7)),
+                Err(Error::InsufficientBond)
+            );
+            bond(&mut oracle, accounts().bob, 1_000).unwrap();
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(8)),
+                Err(Error::UnknownStateRoot)
+            );
+
+            // La clave se deriva de la cuenta: el balance de eve no se acredita a frank
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, eve_proof()),
+                Err(Error::InvalidProof)
+            );
+            assert_eq!(oracle.get_balance(ETHEREUM, frank), Ok(None));
+
+            oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(7)).unwrap();
+            assert_eq!(
+                oracle.get_balance(ETHEREUM, frank),
+                Ok(Some(1_000_000_000_000_000_000_000_000))
+            );
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, frank, frank_proof(7)),
+                Err(Error::StaleProof)
+            );
+
+            oracle.update_balance_with_proof(ETHEREUM, eve, eve_proof()).unwrap();
+            assert_eq!(oracle.get_balance(ETHEREUM, eve), Ok(Some(5_000_000_000_000_000_000)));
+        }
+
+        #[ink::test]
+        fn state_roots_finalize_at_threshold() {
+            let mut oracle = oracle_with_validators(2);
+            let other_root = [7u8; 32];
+
+            set_caller(accounts().eve);
+            assert_eq!(
+                oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000),
+                Err(Error::NotValidator)
+            );
+
+            set_caller(accounts().bob);
+            oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000).unwrap();
+            assert_eq!(
+                oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000),
+                Err(Error::DuplicateSubmission)
+            );
+
+            // Un voto distinto no cuenta para el root de bob
+            set_caller(accounts().charlie);
+            oracle.submit_state_root(ETHEREUM, 10, other_root, 1_000).unwrap();
+            assert_eq!(oracle.get_state_root(ETHEREUM, 10), None);
+            assert_eq!(oracle.get_state_root_votes(ETHEREUM, 10).len(), 2);
+            assert_eq!(oracle.get_latest_height(ETHEREUM), None);
+
+            set_caller(accounts().django);
+            oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000).unwrap();
+            assert_eq!(
+                oracle.get_state_root(ETHEREUM, 10),
+                Some(StateRoot { root: fixture_root(), timestamp: 1_000 })
+            );
+            assert!(oracle.get_state_root_votes(ETHEREUM, 10).is_empty());
+            assert_eq!(oracle.get_latest_height(ETHEREUM), Some(10));
+            assert_eq!(oracle.get_latest_height(ASSET_HUB), None);
+
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000),
+                Err(Error::StateRootFinalized)
+            );
+
+            // Alturas finalizadas fuera de orden no mueven la última
+            for validator in [accounts().bob, accounts().charlie] {
+                set_caller(validator);
+                oracle.submit_state_root(ETHEREUM, 5, other_root, 900).unwrap();
+            }
+            assert_eq!(oracle.get_state_root_heights(ETHEREUM), vec![5, 10]);
+            assert_eq!(oracle.get_latest_height(ETHEREUM), Some(10));
+        }
+
+        #[ink::test]
+        fn state_roots_need_supermajority() {
+            // Umbral 1, pero con 3 validadores hacen falta 2 votos iguales
+            let mut oracle = oracle_with_validators(1);
+
+            set_caller(accounts().bob);
+            oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000).unwrap();
+            assert_eq!(oracle.get_state_root(ETHEREUM, 10), None);
+
+            set_caller(accounts().charlie);
+            oracle.submit_state_root(ETHEREUM, 10, fixture_root(), 1_000).unwrap();
+            assert!(oracle.get_state_root(ETHEREUM, 10).is_some());
+        }
+
+        #[ink::test]
+        fn proof_prices_use_state_root_timestamp() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            oracle.set_price_storage_key(symbol.clone(), storage_slot(FEED_CONTRACT, 0)).unwrap();
+            oracle.set_price_bounds(symbol.clone(), 3_000, 0).unwrap();
+
+            // El root es del header en t = 1_000; el bloque actual es t = 5_000
+            test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            finalize_fixture_root(&mut oracle, 100);
+
+            set_caller(accounts().bob);
+            let update = |oracle: &mut HyperbridgeOracle| {
+                oracle.update_price_with_proof(
+                    symbol.clone(),
+                    ETHEREUM,
+                    String::from(FEED_CONTRACT),
+                    state_proof(100, slot(0), ACCOUNT_PROOF_FEED, STORAGE_PROOF_PRICE),
+                )
+            };
+            assert_eq!(update(&mut oracle), Err(Error::StalePrice));
+
+            set_caller(accounts().alice);
+            oracle.set_price_bounds(symbol.clone(), 10_000, 0).unwrap();
+
+            set_caller(accounts().bob);
+            update(&mut oracle).unwrap();
+            let data = oracle.get_price(symbol).unwrap();
+            assert_eq!(data.price, 750_000_000);
+            assert_eq!(data.timestamp, 1_000);
+        }
+
+        #[ink::test]
+        fn proof_older_than_current_price_is_rejected() {
+            let mut oracle = oracle_with_validators(1);
+            let symbol = String::from("DOT/USD");
+            oracle.set_price_storage_key(symbol.clone(), storage_slot(FEED_CONTRACT, 0)).unwrap();
+
+            // Mediana finalizada en t = 4_000
+            test::set_block_timestamp::<DefaultEnvironment>(4_000);
+            set_caller(accounts().bob);
+            oracle.update_price(symbol.clone(), 1, 100, ETHEREUM, String::from(FEED_CONTRACT)).unwrap();
+            assert_eq!(oracle.get_price(symbol.clone()).unwrap().timestamp, 4_000);
+
+            // El root del proof es del header en t = 1_000
+            finalize_fixture_root(&mut oracle, 100);
+            set_caller(accounts().charlie);
+            assert_eq!(
+                oracle.update_price_with_proof(
+                    symbol.clone(),
+                    ETHEREUM,
+                    String::from(FEED_CONTRACT),
+                    state_proof(100, slot(0), ACCOUNT_PROOF_FEED, STORAGE_PROOF_PRICE),
+                ),
+                Err(Error::StalePrice)
+            );
+
+            let data = oracle.get_price(symbol.clone()).unwrap();
+            assert_eq!((data.price, data.timestamp, data.round_id), (100, 4_000, 1));
+            assert_eq!(oracle.get_current_round(symbol), 2);
+        }
+
+        #[ink::test]
+        fn state_roots_are_pruned() {
+            let mut oracle = oracle_with_validators(1);
+
+            for height in 1..=u64::from(MAX_STATE_ROOTS) + 1 {
+                for validator in [accounts().bob, accounts().charlie] {
+                    set_caller(validator);
+                    oracle.submit_state_root(ETHEREUM, height, fixture_root(), height).unwrap();
+                }
+            }
+
+            let heights = oracle.get_state_root_heights(ETHEREUM);
+            assert_eq!(heights.len(), MAX_STATE_ROOTS as usize);
+            assert_eq!(heights.first(), Some(&2));
+            assert_eq!(oracle.get_state_root(ETHEREUM, 1), None);
+            assert_eq!(oracle.get_latest_height(ETHEREUM), Some(u64::from(MAX_STATE_ROOTS) + 1));
+
+            // La altura podada ya no se acepta ni sirve para proofs
+            assert_eq!(
+                oracle.submit_state_root(ETHEREUM, 1, fixture_root(), 1),
+                Err(Error::StaleStateRoot)
+            );
+            set_caller(accounts().alice);
+            oracle.set_balance_storage_slot(ETHEREUM, storage_slot(FEED_CONTRACT, 2)).unwrap();
+            set_caller(accounts().bob);
+            let proof = state_proof(1, balance_key(accounts().frank), ACCOUNT_PROOF_FEED, STORAGE_PROOF_FRANK);
+            assert_eq!(
+                oracle.update_balance_with_proof(ETHEREUM, accounts().frank, proof),
+                Err(Error::UnknownStateRoot)
+            );
+        }
+
+        #[ink::test]
+        fn chain_registry_works() {
+            let mut oracle = oracle_with_validators(1);
+            let polygon = ChainId::Evm(137);
+
+            set_caller(accounts().bob);
+            assert_eq!(oracle.register_chain(polygon, String::from("Polygon")), Err(Error::NotOwner));
+
+            set_caller(accounts().alice);
+            assert_eq!(oracle.register_chain(polygon, String::new()), Err(Error::InvalidChain));
+            assert_eq!(oracle.remove_chain(polygon), Err(Error::InvalidChain));
+            assert_eq!(
+                oracle.set_feed(
+                    String::from("MATIC/USD"),
+                    String::from("MATIC"),
+                    String::from("USD"),
+                    8,
+                    vec![polygon],
+                ),
+                Err(Error::InvalidChain)
+            );
+
+            // Chain sin registrar
+            assert_eq!(
+                oracle.set_balance_storage_slot(polygon, storage_slot(BEEF_CONTRACT, 0)),
+                Err(Error::InvalidChain)
+            );
+            assert_eq!(oracle.get_balance(polygon, accounts().frank), Err(Error::InvalidChain));
+
+            set_caller(accounts().alice);
+            oracle.register_chain(polygon, String::from("Polygon")).unwrap();
+            assert_eq!(oracle.get_chain(polygon), Some(String::from("Polygon")));
+            assert_eq!(oracle.list_chains().len(), 3);
+
+            oracle.set_balance_storage_slot(polygon, storage_slot(BEEF_CONTRACT, 0)).unwrap();
+            assert_eq!(oracle.get_balance(polygon, accounts().frank), Ok(None));
+
+            // Registrada pero no permitida por el feed
+            set_caller(accounts().bob);
+            assert_eq!(
+                oracle.update_price(String::from("DOT/USD"), 1, 100, polygon, String::from("0xfeed")),
+                Err(Error::InvalidChain)
+            );
+
+            // El mismo número de id en otra familia es otra chain
+            assert_eq!(oracle.get_balance(ChainId::Substrate(137), accounts().frank), Err(Error::InvalidChain));
+
+            set_caller(accounts().alice);
+            oracle.remove_chain(polygon).unwrap();
+            assert_eq!(oracle.get_chain(polygon), None);
+            assert_eq!(
+                oracle.list_chains(),
+                vec![
+                    (ETHEREUM, String::from("Ethereum")),
+                    (ASSET_HUB, String::from("Polkadot Asset Hub")),
+                ]
+            );
+            assert_eq!(oracle.get_balance(polygon, accounts().frank), Err(Error::InvalidChain));
+        }
     }
 }
 
@@ -0,0 +1,162 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Price Consumer - ejemplo de integración con HyperbridgeOracle
+///
+/// Lee precios cross-contract a través del trait `PriceOracle` y los usa
+/// para valorar una cantidad de un activo en la moneda de cotización.
+///
+/// Generado con Polkadot DevKit para sub0 Hackathon 2025
+#[ink::contract]
+mod price_consumer {
+    use hyperbridge_oracle::PriceOracle;
+    use ink::prelude::string::String;
+
+    /// Storage del contrato
+    #[ink(storage)]
+    pub struct PriceConsumer {
+        /// Oracle consultado
+        oracle: ink::contract_ref!(PriceOracle),
+    }
+
+    impl PriceConsumer {
+        /// Constructor del contrato
+        #[ink(constructor)]
+        pub fn new(oracle: AccountId) -> Self {
+            Self {
+                oracle: oracle.into(),
+            }
+        }
+
+        /// Obtener el último precio fresco de un símbolo
+        #[ink(message)]
+        pub fn latest_price(&self, symbol: String) -> Result<u128, Error> {
+            self.oracle.latest_price(symbol)
+                .map(|price_data| price_data.price)
+                .map_err(Error::Oracle)
+        }
+
+        /// Valorar `amount` unidades del activo base en la moneda de cotización
+        ///
+        /// `amount` y el resultado usan los decimales del feed.
+        #[ink(message)]
+        pub fn quote(&self, symbol: String, amount: u128) -> Result<u128, Error> {
+            let price_data = self.oracle.latest_price(symbol).map_err(Error::Oracle)?;
+            let scale = 10u128
+                .checked_pow(u32::from(price_data.decimals))
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            amount
+                .checked_mul(price_data.price)
+                .map(|value| value / scale)
+                .ok_or(Error::ArithmeticOverflow)
+        }
+    }
+
+    /// Errores del contrato
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Error devuelto por el oracle
+        Oracle(hyperbridge_oracle::Error),
+        ArithmeticOverflow,
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use hyperbridge_oracle::{ChainId, HyperbridgeOracle, HyperbridgeOracleRef};
+        use ink_e2e::ContractsBackend;
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn reads_price_cross_contract<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let mut oracle_constructor = HyperbridgeOracleRef::new(1);
+            let oracle = client
+                .instantiate("hyperbridge_oracle", &ink_e2e::alice(), &mut oracle_constructor)
+                .submit()
+                .await
+                .expect("oracle instantiate failed");
+            let mut oracle_builder = oracle.call_builder::<HyperbridgeOracle>();
+
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let add_validator = oracle_builder.add_validator(alice);
+            client
+                .call(&ink_e2e::alice(), &add_validator)
+                .submit()
+                .await
+                .expect("add_validator failed");
+
+            let register_chain = oracle_builder.register_chain(ChainId::Evm(1), String::from("Ethereum"));
+            client
+                .call(&ink_e2e::alice(), &register_chain)
+                .submit()
+                .await
+                .expect("register_chain failed");
+
+            let set_feed = oracle_builder.set_feed(
+                String::from("DOT/USD"),
+                String::from("DOT"),
+                String::from("USD"),
+                2,
+                vec![ChainId::Evm(1)],
+            );
+            client
+                .call(&ink_e2e::alice(), &set_feed)
+                .submit()
+                .await
+                .expect("set_feed failed");
+
+            // 7.50 USD con 2 decimales
+            let update_price = oracle_builder.update_price(
+                String::from("DOT/USD"),
+                1,
+                750,
+                ChainId::Evm(1),
+                String::from("0xfeed"),
+            );
+            client
+                .call(&ink_e2e::alice(), &update_price)
+                .submit()
+                .await
+                .expect("update_price failed");
+
+            let mut constructor = PriceConsumerRef::new(oracle.account_id);
+            let consumer = client
+                .instantiate("price_consumer", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("consumer instantiate failed");
+            let call_builder = consumer.call_builder::<PriceConsumer>();
+
+            let latest_price = call_builder.latest_price(String::from("DOT/USD"));
+            let price = client
+                .call(&ink_e2e::alice(), &latest_price)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(price, Ok(750));
+
+            // 2.00 DOT a 7.50 USD = 15.00 USD
+            let quote = call_builder.quote(String::from("DOT/USD"), 200);
+            let value = client
+                .call(&ink_e2e::alice(), &quote)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(value, Ok(1_500));
+
+            let missing = call_builder.latest_price(String::from("ETH/USD"));
+            let result = client
+                .call(&ink_e2e::alice(), &missing)
+                .dry_run()
+                .await?
+                .return_value();
+            assert_eq!(result, Err(Error::Oracle(hyperbridge_oracle::Error::PriceNotFound)));
+
+            Ok(())
+        }
+    }
+}